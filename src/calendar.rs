@@ -0,0 +1,180 @@
+//! The advent calendar: when each day's puzzle unlocks, and how many stars have been collected on
+//! each day, as shown by the calendar page of each year
+//!
+//! Puzzles unlock at midnight in UTC-5 (EST), on each of the first 25 days of December.
+use chrono::{DateTime, Datelike, Duration, FixedOffset, TimeZone, Utc};
+use std::{collections::BTreeMap, future::Future};
+
+/// The timezone the calendar follows, which does not observe daylight saving
+pub fn timezone() -> FixedOffset {
+    FixedOffset::west_opt(5 * 3600).expect("UTC-5 is a valid offset")
+}
+
+/// When the puzzle for a day unlocks
+pub fn unlock_time(year: i32, day: u32) -> Option<DateTime<Utc>> {
+    let unlock = timezone()
+        .with_ymd_and_hms(year, 12, day, 0, 0, 0)
+        .single()?;
+    Some(unlock.with_timezone(&Utc))
+}
+
+/// The most recent day whose puzzle has unlocked, as a year and a day
+pub fn recent_day(now: DateTime<Utc>) -> (i32, u32) {
+    let date = now.with_timezone(&timezone()).date_naive();
+    if date.month() < 12 {
+        // it's not december yet, so last year's calendar is the most recent one
+        (date.year() - 1, 25)
+    } else {
+        (date.year(), date.day().min(25))
+    }
+}
+
+/// The next day to unlock after the given time, as a year and a day
+pub fn next_day(now: DateTime<Utc>) -> (i32, u32) {
+    let date = now.with_timezone(&timezone()).date_naive();
+    match (date.month(), date.day()) {
+        (12, day) if day < 25 => (date.year(), day + 1),
+        (12, _) => (date.year() + 1, 1),
+        _ => (date.year(), 1),
+    }
+}
+
+/// A source of the current time, which can be replaced to test anything that waits on the calendar
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: std::time::Duration) -> impl Future<Output = ()>;
+}
+
+/// The system's clock
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: std::time::Duration) -> impl Future<Output = ()> {
+        tokio::time::sleep(duration)
+    }
+}
+
+/// Sleeps until the given time, calling `tick` with the time remaining about once a second
+pub async fn wait_until(clock: &impl Clock, time: DateTime<Utc>, mut tick: impl FnMut(Duration)) {
+    loop {
+        let remaining = time - clock.now();
+        if remaining <= Duration::zero() {
+            return;
+        }
+        tick(remaining);
+        let step = remaining.min(Duration::seconds(1));
+        clock.sleep(step.to_std().unwrap_or_default()).await;
+    }
+}
+
+/// Reads the number of stars collected on each day from a calendar page, where each day links to
+/// its puzzle with a class of `calendar-complete` for one star or `calendar-verycomplete` for two
+pub fn stars(page: &str) -> BTreeMap<u32, u8> {
+    let mut stars = BTreeMap::new();
+    for link in page.split("<a ").skip(1) {
+        let Some(start) = link.find("class=\"").map(|start| start + 7) else {
+            continue;
+        };
+        let Some(end) = link[start..].find('"') else {
+            continue;
+        };
+        let classes = link[start..start + end].split_whitespace();
+        let mut day = None;
+        let mut count = 0;
+        for class in classes {
+            match class {
+                "calendar-verycomplete" => count = 2,
+                "calendar-complete" => count = count.max(1),
+                class => {
+                    if let Some(number) = class.strip_prefix("calendar-day") {
+                        day = number.parse().ok().or(day);
+                    }
+                }
+            }
+        }
+        if let Some(day) = day {
+            stars.insert(day, count);
+        }
+    }
+    stars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn day_test() {
+        assert_eq!(unlock_time(2021, 1), Some(utc("2021-12-01T05:00:00Z")));
+        assert_eq!(unlock_time(2021, 32), None);
+        let days = |now| (recent_day(utc(now)), next_day(utc(now)));
+        // the day changes at 05:00 UTC, not at midnight UTC
+        assert_eq!(days("2021-12-03T04:59:59Z"), ((2021, 2), (2021, 3)));
+        assert_eq!(days("2021-12-03T05:00:00Z"), ((2021, 3), (2021, 4)));
+        assert_eq!(days("2021-12-01T04:00:00Z"), ((2020, 25), (2021, 1)));
+        assert_eq!(days("2021-06-15T12:00:00Z"), ((2020, 25), (2021, 1)));
+        assert_eq!(days("2021-12-25T05:00:00Z"), ((2021, 25), (2022, 1)));
+        assert_eq!(days("2021-12-31T12:00:00Z"), ((2021, 25), (2022, 1)));
+        assert_eq!(days("2022-01-01T02:00:00Z"), ((2021, 25), (2022, 1)));
+    }
+
+    /// A clock which only moves when it is slept on
+    struct TestClock {
+        now: Cell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<std::time::Duration>>,
+    }
+
+    impl Clock for TestClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: std::time::Duration) -> impl Future<Output = ()> {
+            self.now
+                .set(self.now.get() + Duration::from_std(duration).unwrap());
+            self.sleeps.borrow_mut().push(duration);
+            std::future::ready(())
+        }
+    }
+
+    #[tokio::test]
+    async fn wait_test() {
+        let clock = TestClock {
+            now: Cell::new(utc("2021-12-01T04:59:57.500Z")),
+            sleeps: RefCell::new(Vec::new()),
+        };
+        let mut ticks = Vec::new();
+        wait_until(&clock, utc("2021-12-01T05:00:00Z"), |remaining| {
+            ticks.push(remaining.num_milliseconds())
+        })
+        .await;
+        assert_eq!(ticks, [2500, 1500, 500]);
+        assert_eq!(clock.now(), utc("2021-12-01T05:00:00Z"));
+        assert_eq!(clock.sleeps.borrow().len(), 3);
+
+        // waiting for a time which has passed returns at once
+        wait_until(&clock, utc("2021-11-30T00:00:00Z"), |_| panic!()).await;
+        assert_eq!(clock.sleeps.borrow().len(), 3);
+    }
+
+    #[test]
+    fn stars_test() {
+        let page = r#"<main>
+<pre class="calendar"><a aria-label="Day 1, two stars" href="/2021/day/1" class="calendar-day1 calendar-verycomplete">  <span class="calendar-day"> 1</span> <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<a aria-label="Day 2, one star" href="/2021/day/2" class="calendar-day2 calendar-complete"><span class="calendar-day"> 2</span></a>
+<a aria-label="Day 3" href="/2021/day/3" class="calendar-day3"><span class="calendar-day"> 3</span></a>
+<span aria-hidden="true" class="calendar-day4">   <span class="calendar-day"> 4</span></span>
+<a href="/2021/about">[About]</a>
+</pre></main>"#;
+        assert_eq!(stars(page), BTreeMap::from([(1, 2), (2, 1), (3, 0)]));
+    }
+}
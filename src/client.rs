@@ -0,0 +1,506 @@
+//! A polite client for the advent of code website, used by the `util` binary
+//!
+//! Every request identifies itself with a User-Agent, and requests are spaced out by a throttle
+//! which is shared between runs, so that scripted use of the tooling never hammers the server.
+use crate::{Part, Result};
+use hyper::{
+    body,
+    client::{Builder, HttpConnector},
+    header::{CONTENT_TYPE, COOKIE, USER_AGENT},
+    Body, Method, Request, StatusCode,
+};
+use hyper_tls::HttpsConnector;
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_USER_AGENT: &str = concat!("aoc-util/", env!("CARGO_PKG_VERSION"));
+pub const DEFAULT_THROTTLE: Duration = Duration::from_secs(5);
+
+/// The contents of `Advent.toml`
+#[derive(serde::Deserialize, Debug)]
+pub struct Config {
+    pub config: ConfigInner,
+}
+#[derive(serde::Deserialize, Debug)]
+pub struct ConfigInner {
+    pub session: String,
+    /// Sent with every request, the website asks that this includes a way to contact you
+    pub user_agent: Option<String>,
+    /// Where the website is, which may be changed to test against a local server
+    pub base_url: Option<String>,
+    /// The least number of seconds to leave between requests
+    pub throttle_secs: Option<f64>,
+}
+impl Config {
+    pub fn load(path: impl AsRef<Path>) -> Result<Config> {
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+/// A response from the website which is not what was asked for
+#[derive(Clone, PartialEq, Eq)]
+pub enum ClientError {
+    /// The puzzle has not unlocked yet
+    NotUnlocked,
+    /// The session token is missing, invalid or expired
+    LoggedOut,
+    /// Any other unsuccessful response, with the start of its body
+    Status(u16, String),
+}
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::NotUnlocked => write!(f, "That puzzle has not unlocked yet"),
+            ClientError::LoggedOut => write!(
+                f,
+                "Not logged in, check the session token in Advent.toml has not expired"
+            ),
+            ClientError::Status(status, body) => {
+                write!(f, "The server responded with status {}: {}", status, body)
+            }
+        }
+    }
+}
+// errors returned from `main` are printed with `Debug`, so it gives the readable message
+impl fmt::Debug for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+impl std::error::Error for ClientError {}
+
+const NOT_UNLOCKED: &str = "before it unlocks";
+const LOGGED_OUT: &str = "Please log in";
+
+/// Recognises the pages the website shows instead of a puzzle which has not unlocked, or to someone
+/// who is not logged in
+fn page_error(body: &str) -> Option<ClientError> {
+    if body.contains(NOT_UNLOCKED) {
+        Some(ClientError::NotUnlocked)
+    } else if body.contains(LOGGED_OUT) {
+        Some(ClientError::LoggedOut)
+    } else {
+        None
+    }
+}
+
+/// Checks a response is successful, and if not, why
+///
+/// Successful bodies are not searched for the pages in [`page_error`], as they can contain text
+/// chosen by anyone, such as the names in a leaderboard.
+fn check_response(status: StatusCode, body: &str) -> std::result::Result<(), ClientError> {
+    if status.is_success() {
+        return Ok(());
+    }
+    match page_error(body) {
+        Some(error) => Err(error),
+        None => {
+            let snippet = body.lines().next().unwrap_or_default();
+            let snippet: String = snippet.chars().take(100).collect();
+            Err(ClientError::Status(status.as_u16(), snippet))
+        }
+    }
+}
+
+/// The website's judgement of a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which way
+    Wrong,
+    /// An answer was submitted too recently, the answer was not checked
+    Wait(u64),
+    /// The part has already been solved, the answer was not checked
+    AlreadySolved,
+}
+impl Verdict {
+    /// Reads the verdict from the page returned by submitting an answer
+    pub fn parse(page: &str) -> Option<Verdict> {
+        if page.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if page.contains("That's not the right answer") {
+            Some(if page.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if page.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            })
+        } else if page.contains("You gave an answer too recently") {
+            // such as "You have 1m 3s left to wait."
+            let start = page.find("You have ")? + "You have ".len();
+            let end = start + page[start..].find(" left to wait")?;
+            let seconds = page[start..end]
+                .split_whitespace()
+                .map(|amount| match amount.split_at(amount.len() - 1) {
+                    (minutes, "m") => minutes.parse::<u64>().ok().map(|m| m * 60),
+                    (seconds, "s") => seconds.parse().ok(),
+                    _ => None,
+                })
+                .sum::<Option<u64>>()?;
+            Some(Verdict::Wait(seconds))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Verdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the answer was actually checked, rather than the submission being turned away
+    pub fn is_judgement(self) -> bool {
+        !matches!(self, Verdict::Wait(_) | Verdict::AlreadySolved)
+    }
+}
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(seconds) => write!(f, "submitted too recently, wait {}s", seconds),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Encodes a value for an `application/x-www-form-urlencoded` body
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            b' ' => "+".to_string(),
+            byte => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    user_agent: String,
+    throttle: Duration,
+    /// Where the time of the last request is recorded, so that the throttle applies across runs
+    throttle_file: Option<PathBuf>,
+    last_request: Cell<Option<SystemTime>>,
+    http: hyper::Client<HttpsConnector<HttpConnector>>,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Client {
+        let config = &config.config;
+        Client {
+            base_url: config
+                .base_url
+                .as_deref()
+                .unwrap_or(DEFAULT_BASE_URL)
+                .trim_end_matches('/')
+                .to_string(),
+            session: config.session.clone(),
+            user_agent: config
+                .user_agent
+                .clone()
+                .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string()),
+            throttle: config
+                .throttle_secs
+                .map_or(DEFAULT_THROTTLE, Duration::from_secs_f64),
+            throttle_file: None,
+            last_request: Cell::new(None),
+            http: Builder::default().build(HttpsConnector::new()),
+        }
+    }
+
+    /// Records the time of each request in a file, so that the throttle also covers other runs
+    pub fn with_throttle_file(mut self, path: impl Into<PathBuf>) -> Client {
+        self.throttle_file = Some(path.into());
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Waits until the throttle allows another request, then records that one is being made
+    async fn throttle(&self) -> Result<()> {
+        let recorded = self
+            .throttle_file
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|secs| secs.trim().parse().ok())
+            .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs_f64(secs));
+        let last = self.last_request.get().max(recorded);
+        if let Some(elapsed) = last.and_then(|last| last.elapsed().ok()) {
+            if elapsed < self.throttle {
+                tokio::time::sleep(self.throttle - elapsed).await;
+            }
+        }
+        let now = SystemTime::now();
+        self.last_request.set(Some(now));
+        if let Some(path) = &self.throttle_file {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let secs = now.duration_since(SystemTime::UNIX_EPOCH)?.as_secs_f64();
+            fs::write(path, secs.to_string())?;
+        }
+        Ok(())
+    }
+
+    async fn request(&self, method: Method, path: &str, form: Option<String>) -> Result<String> {
+        self.throttle().await?;
+        let request = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.base_url, path))
+            .header(COOKIE, format!("session={}", self.session))
+            .header(USER_AGENT, &self.user_agent);
+        let request = match form {
+            Some(form) => request
+                .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(form))?,
+            None => request.body(Body::empty())?,
+        };
+        let response = self.http.request(request).await?;
+        let status = response.status();
+        let bytes = body::to_bytes(response.into_body()).await?;
+        let body = String::from_utf8(bytes.to_vec())?;
+        check_response(status, &body)?;
+        Ok(body)
+    }
+
+    /// Fetches a page of the website, such as `/2021/day/1`
+    pub async fn get(&self, path: &str) -> Result<String> {
+        self.request(Method::GET, path, None).await
+    }
+
+    /// Posts a form to the website, given as pairs of field names and values
+    pub async fn post(&self, path: &str, fields: &[(&str, &str)]) -> Result<String> {
+        let form = fields
+            .iter()
+            .map(|(name, value)| format!("{}={}", form_encode(name), form_encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        self.request(Method::POST, path, Some(form)).await
+    }
+
+    /// Fetches the input for a day
+    pub async fn input(&self, year: i32, day: u32) -> Result<String> {
+        let input = self.get(&format!("/{}/day/{}/input", year, day)).await?;
+        // an input is never a page, so one in its place is an error however it was served
+        match page_error(&input) {
+            Some(error) => Err(error.into()),
+            None => Ok(input),
+        }
+    }
+
+    /// Fetches the puzzle description for a day, converted to Markdown
+    pub async fn puzzle(&self, year: i32, day: u32) -> Result<String> {
+        let page = self.get(&format!("/{}/day/{}", year, day)).await?;
+        Ok(crate::puzzle::to_markdown(&page, &self.base_url)
+            .ok_or("The puzzle page did not contain a description")?)
+    }
+
+    /// Fetches the number of stars collected on each day of a year, from its calendar
+    pub async fn stars(&self, year: i32) -> Result<BTreeMap<u32, u8>> {
+        let page = self.get(&format!("/{}", year)).await?;
+        Ok(crate::calendar::stars(&page))
+    }
+
+    /// Fetches a private leaderboard as JSON, which should be requested at most once every 15
+    /// minutes, so is normally loaded through [`crate::leaderboard::Leaderboard::load`]
+    pub async fn leaderboard(&self, year: i32, id: u64) -> Result<String> {
+        self.get(&format!("/{}/leaderboard/private/view/{}.json", year, id))
+            .await
+    }
+
+    /// Submits an answer to one part of a day
+    pub async fn submit(&self, year: i32, day: u32, part: Part, answer: &str) -> Result<Verdict> {
+        let level = match part {
+            Part::One => "1",
+            Part::Two => "2",
+        };
+        let page = self
+            .post(
+                &format!("/{}/day/{}/answer", year, day),
+                &[("level", level), ("answer", answer)],
+            )
+            .await?;
+        Ok(Verdict::parse(&page).ok_or("Could not understand the response to that answer")?)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Instant,
+    };
+
+    /// Serves each given response in turn from a local server, sending back each request received
+    pub(crate) fn stub_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (requests, received) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.send(request).unwrap();
+                let response = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, received)
+    }
+
+    pub(crate) fn stub_client(url: &str, throttle_secs: f64) -> Client {
+        Client::new(&Config {
+            config: ConfigInner {
+                session: "abc123".to_string(),
+                user_agent: Some("tests".to_string()),
+                base_url: Some(url.to_string()),
+                throttle_secs: Some(throttle_secs),
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn input_test() {
+        let (url, requests) = stub_server(vec![
+            (200, "1\n2\n3\n"),
+            (404, "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time."),
+            (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input."),
+            (500, "Internal Server Error\nmore"),
+            (200, "Puzzle inputs differ by user.  Please log in to get your puzzle input."),
+        ]);
+        let client = stub_client(&url, 0.0);
+        assert_eq!(client.input(2021, 1).await.unwrap(), "1\n2\n3\n");
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2021/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("cookie: session=abc123\r\n"));
+        assert!(request.contains("user-agent: tests\r\n"));
+
+        let error =
+            |result: Result<String>| *result.unwrap_err().downcast::<ClientError>().unwrap();
+        assert_eq!(error(client.input(2021, 2).await), ClientError::NotUnlocked);
+        assert_eq!(error(client.input(2021, 3).await), ClientError::LoggedOut);
+        assert_eq!(
+            error(client.input(2021, 4).await),
+            ClientError::Status(500, "Internal Server Error".to_string())
+        );
+        assert_eq!(error(client.input(2021, 5).await), ClientError::LoggedOut);
+    }
+
+    #[tokio::test]
+    async fn successful_page_test() {
+        // a leaderboard member can be named anything, including what the error pages say
+        let json = r#"{"members": {"1": {"name": "Please log in before it unlocks"}}}"#;
+        let (url, _requests) = stub_server(vec![(200, json)]);
+        let client = stub_client(&url, 0.0);
+        assert_eq!(client.leaderboard(2021, 1).await.unwrap(), json);
+    }
+
+    #[tokio::test]
+    async fn submit_test() {
+        let (url, requests) = stub_server(vec![
+            (200, "<article><p>That's the right answer!  You are one gold star closer.</p></article>"),
+            (200, "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.</p></article>"),
+            (200, "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 3s left to wait.</p></article>"),
+            (200, "<html>Something else</html>"),
+        ]);
+        let client = stub_client(&url, 0.0);
+        assert_eq!(
+            client.submit(2021, 1, Part::One, "12 & 3").await.unwrap(),
+            Verdict::Correct
+        );
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2021/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.contains("content-type: application/x-www-form-urlencoded\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1&answer=12+%26+3"));
+        assert_eq!(
+            client.submit(2021, 1, Part::Two, "5").await.unwrap(),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            client.submit(2021, 1, Part::Two, "5").await.unwrap(),
+            Verdict::Wait(63)
+        );
+        assert!(client.submit(2021, 1, Part::Two, "5").await.is_err());
+    }
+
+    #[test]
+    fn verdict_test() {
+        let wrong = "That's not the right answer.  If you're stuck, please wait one minute";
+        assert_eq!(Verdict::parse(wrong), Some(Verdict::Wrong));
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently. You have 34s left to wait."),
+            Some(Verdict::Wait(34))
+        );
+        assert_eq!(
+            Verdict::parse(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Some(Verdict::AlreadySolved)
+        );
+    }
+
+    #[tokio::test]
+    async fn throttle_test() {
+        let (url, _requests) = stub_server(vec![(200, "a"), (200, "b")]);
+        let throttle_file =
+            std::env::temp_dir().join(format!("aoc-throttle-test-{}", std::process::id()));
+        let _ = fs::remove_file(&throttle_file);
+        let client = stub_client(&url, 0.2).with_throttle_file(&throttle_file);
+        let start = Instant::now();
+        client.get("/a").await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(200));
+        assert!(throttle_file.exists());
+        // a new client still waits for the request made by the previous one
+        let client = stub_client(&url, 0.2).with_throttle_file(&throttle_file);
+        client.get("/b").await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        fs::remove_file(&throttle_file).unwrap();
+    }
+}
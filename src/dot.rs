@@ -0,0 +1,127 @@
+//! Graphviz DOT export, for visualising the graph shaped advent of code problems
+//!
+//! The output can be rendered with, for example, `dot -Tsvg graph.dot -o graph.svg`.
+use std::fmt;
+
+/// Attributes of a node or edge, such as `("shape", "box".to_string())`
+pub type Attributes = Vec<(&'static str, String)>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dot {
+    directed: bool,
+    statements: Vec<String>,
+}
+
+impl Dot {
+    /// A graph whose edges are drawn as arrows
+    pub fn directed() -> Dot {
+        Dot {
+            directed: true,
+            statements: Vec::new(),
+        }
+    }
+
+    /// A graph whose edges are drawn as plain lines
+    pub fn undirected() -> Dot {
+        Dot {
+            directed: false,
+            statements: Vec::new(),
+        }
+    }
+
+    /// Adds a node, nodes which are only referred to by edges do not need to be added
+    pub fn node(&mut self, id: impl fmt::Display, attributes: Attributes) -> &mut Dot {
+        let statement = format!("{}{}", quote(id), attribute_list(&attributes));
+        self.statements.push(statement);
+        self
+    }
+
+    pub fn edge(
+        &mut self,
+        from: impl fmt::Display,
+        to: impl fmt::Display,
+        attributes: Attributes,
+    ) -> &mut Dot {
+        let arrow = if self.directed { "->" } else { "--" };
+        let statement = format!(
+            "{} {} {}{}",
+            quote(from),
+            arrow,
+            quote(to),
+            attribute_list(&attributes)
+        );
+        self.statements.push(statement);
+        self
+    }
+}
+
+impl fmt::Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {{", if self.directed { "digraph" } else { "graph" })?;
+        for statement in &self.statements {
+            writeln!(f, "    {};", statement)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// Quotes an id or attribute value, so that it may contain any characters
+fn quote(text: impl fmt::Display) -> String {
+    let text = text.to_string();
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn attribute_list(attributes: &Attributes) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+    let attributes = attributes
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, quote(value)))
+        .collect::<Vec<_>>();
+    format!(" [{}]", attributes.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+
+    #[test]
+    fn dot_test() {
+        let mut dot = Dot::directed();
+        dot.node("a", vec![("label", "say \"hi\"".to_string())])
+            .edge("a", "b\\c", vec![])
+            .edge("b\\c", "a", vec![("color", "red".to_string())]);
+        assert_eq!(
+            dot.to_string(),
+            "digraph {\n    \"a\" [label=\"say \\\"hi\\\"\"];\n    \"a\" -> \"b\\\\c\";\n    \
+             \"b\\\\c\" -> \"a\" [color=\"red\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn graph_test() {
+        let mut graph = Graph::new();
+        graph.add_undirected_edge("start", "A", 1);
+        graph.add_undirected_edge("A", "end", 2);
+        let undirected = graph.to_dot(false, |_| vec![], |_| vec![]);
+        assert_eq!(
+            undirected.to_string(),
+            "graph {\n    \"0\" [label=\"start\"];\n    \"1\" [label=\"A\"];\n    \
+             \"2\" [label=\"end\"];\n    \"0\" -- \"1\";\n    \"1\" -- \"2\";\n}\n"
+        );
+        let directed = graph.to_dot(
+            true,
+            |&node| match node {
+                "A" => vec![("label", "big".to_string())],
+                _ => vec![],
+            },
+            |weight| vec![("label", weight.to_string())],
+        );
+        let directed = directed.to_string();
+        assert!(directed.contains("\"1\" [label=\"big\"];"));
+        assert!(directed.contains("\"2\" -> \"1\" [label=\"2\"];"));
+        assert_eq!(directed.matches("->").count(), 4);
+    }
+}
@@ -0,0 +1,543 @@
+//! Graphs and the search algorithms run over them, used by the many pathfinding advent of code
+//! problems
+//!
+//! The algorithms take a `successors` function rather than a [`Graph`], so that they can be run
+//! as easily over implicit graphs such as a [`Grid`](crate::grid::Grid) as over a built graph.
+use crate::dot::{Attributes, Dot};
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt,
+    hash::Hash,
+    ops::Add,
+};
+
+/// The index of a node within a [`Graph`]
+pub type NodeId = usize;
+
+/// A directed graph with weighted edges, whose nodes are interned so they can be referred to by id
+#[derive(Debug, Clone)]
+pub struct Graph<N, W = ()> {
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    edges: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<N, W> Default for Graph<N, W> {
+    fn default() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash, W: Copy> Graph<N, W> {
+    pub fn new() -> Graph<N, W> {
+        Graph::default()
+    }
+
+    /// Gets the id of a node, adding it to the graph if it is not already present
+    pub fn add_node(&mut self, node: N) -> NodeId {
+        match self.ids.entry(node) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let id = self.nodes.len();
+                self.nodes.push(entry.key().clone());
+                self.edges.push(Vec::new());
+                entry.insert(id);
+                id
+            }
+        }
+    }
+
+    /// Adds an edge from one node to another, adding either node if not already present
+    pub fn add_edge(&mut self, from: N, to: N, weight: W) -> (NodeId, NodeId) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.edges[from].push((to, weight));
+        (from, to)
+    }
+
+    /// Adds an edge in both directions between two nodes
+    pub fn add_undirected_edge(&mut self, a: N, b: N, weight: W) -> (NodeId, NodeId) {
+        let (a, b) = self.add_edge(a, b, weight);
+        self.edges[b].push((a, weight));
+        (a, b)
+    }
+
+    pub fn id(&self, node: &N) -> Option<NodeId> {
+        self.ids.get(node).copied()
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = NodeId> {
+        0..self.nodes.len()
+    }
+
+    /// Every edge out of a node, alongside its weight
+    pub fn edges(&self, id: NodeId) -> impl Iterator<Item = (NodeId, W)> + '_ {
+        self.edges[id].iter().copied()
+    }
+
+    /// The weight of the first edge from one node to another, if there is one
+    pub fn weight(&self, from: NodeId, to: NodeId) -> Option<W> {
+        self.edges(from)
+            .find(|&(id, _)| id == to)
+            .map(|(_, weight)| weight)
+    }
+
+    /// Every node with an edge from a node
+    pub fn successors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[id].iter().map(|&(to, _)| to)
+    }
+
+    /// The same graph with every edge reversed, node ids are preserved
+    pub fn reversed(&self) -> Graph<N, W> {
+        let mut edges = vec![Vec::new(); self.len()];
+        for from in self.ids() {
+            for (to, weight) in self.edges(from) {
+                edges[to].push((from, weight));
+            }
+        }
+        Graph {
+            nodes: self.nodes.clone(),
+            ids: self.ids.clone(),
+            edges,
+        }
+    }
+
+    /// Orders the nodes such that every edge points forwards, failing with a cycle
+    pub fn topological_sort(&self) -> Result<Vec<NodeId>, Vec<NodeId>> {
+        topological_sort(self.ids(), |&id| self.successors(id))
+    }
+
+    /// Groups nodes which are connected, treating every edge as undirected
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let undirected = self.reversed();
+        connected_components(self.ids(), |&id| {
+            self.successors(id).chain(undirected.successors(id))
+        })
+    }
+}
+
+impl<N: Clone + Eq + Hash + fmt::Display, W: Copy> Graph<N, W> {
+    /// Exports the graph in Graphviz DOT format, with the given attributes for each node and edge
+    ///
+    /// Nodes are labelled with their display unless given another label. An undirected export
+    /// draws a pair of edges in opposite directions, as added by
+    /// [`add_undirected_edge`](Graph::add_undirected_edge), as a single line.
+    pub fn to_dot(
+        &self,
+        directed: bool,
+        mut node_attributes: impl FnMut(&N) -> Attributes,
+        mut edge_attributes: impl FnMut(W) -> Attributes,
+    ) -> Dot {
+        let mut dot = if directed {
+            Dot::directed()
+        } else {
+            Dot::undirected()
+        };
+        for id in self.ids() {
+            let node = self.node(id);
+            let mut attributes = node_attributes(node);
+            if !attributes.iter().any(|&(name, _)| name == "label") {
+                attributes.insert(0, ("label", node.to_string()));
+            }
+            dot.node(id, attributes);
+        }
+        let mut drawn = HashSet::new();
+        for from in self.ids() {
+            for (to, weight) in self.edges(from) {
+                if !directed && !drawn.insert((from.min(to), from.max(to))) {
+                    continue;
+                }
+                dot.edge(from, to, edge_attributes(weight));
+            }
+        }
+        dot
+    }
+}
+
+/// Finds the shortest path by number of steps from a start to a node satisfying `goal`
+pub fn bfs<N, S>(
+    start: N,
+    mut successors: impl FnMut(&N) -> S,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            return Some(reconstruct_path(&parents, node));
+        }
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                entry.insert(Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Finds the number of steps to every node reachable from a start
+pub fn bfs_distances<N, S>(start: N, mut successors: impl FnMut(&N) -> S) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Lists every node reachable from a start, in depth first order
+pub fn dfs<N, S>(start: N, mut successors: impl FnMut(&N) -> S) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+    S::IntoIter: DoubleEndedIterator,
+{
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        // pushed in reverse so that the first successor is visited first
+        stack.extend(
+            successors(&node)
+                .into_iter()
+                .rev()
+                .filter(|next| !visited.contains(next)),
+        );
+        order.push(node);
+    }
+    order
+}
+
+/// Finds the set of every node reachable from a start, including the start itself
+pub fn flood_fill<N, S>(start: N, mut successors: impl FnMut(&N) -> S) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for next in successors(&node) {
+            if visited.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+    visited
+}
+
+/// Finds the cheapest path from a start to a node satisfying `goal`, alongside its cost
+pub fn dijkstra<N, W, S>(
+    start: N,
+    successors: impl FnMut(&N) -> S,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, W)>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Default + Add<Output = W>,
+    S: IntoIterator<Item = (N, W)>,
+{
+    astar(start, successors, |_| W::default(), goal)
+}
+
+/// Finds the cheapest path from a start to a node satisfying `goal`, alongside its cost, guided by
+/// a heuristic which must never overestimate the remaining cost
+pub fn astar<N, W, S>(
+    start: N,
+    mut successors: impl FnMut(&N) -> S,
+    mut heuristic: impl FnMut(&N) -> W,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, W)>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Default + Add<Output = W>,
+    S: IntoIterator<Item = (N, W)>,
+{
+    // nodes are stored by index in the heap, so that they do not need to be `Ord`
+    let mut nodes = vec![start.clone()];
+    let mut costs = HashMap::from([(start.clone(), (W::default(), 0))]);
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), W::default(), 0))]);
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let node = nodes[index].clone();
+        if goal(&node) {
+            return Some((reconstruct_path(&parents, node), cost));
+        }
+        if costs.get(&node).is_some_and(|&(best, _)| cost > best) {
+            continue;
+        }
+        for (next, weight) in successors(&node) {
+            let next_cost = cost + weight;
+            let next_index = match costs.get(&next) {
+                Some(&(best, _)) if best <= next_cost => continue,
+                Some(&(_, index)) => index,
+                None => {
+                    nodes.push(next.clone());
+                    nodes.len() - 1
+                }
+            };
+            costs.insert(next.clone(), (next_cost, next_index));
+            parents.insert(next.clone(), Some(node.clone()));
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                next_index,
+            )));
+        }
+    }
+    None
+}
+
+fn reconstruct_path<N: Clone + Eq + Hash>(parents: &HashMap<N, Option<N>>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Orders nodes such that every successor comes after its predecessors
+///
+/// If there is no such order, fails with a cycle, listed in the direction of its edges such that the
+/// last node leads back to the first, and starting from whichever of its nodes was given first.
+pub fn topological_sort<N, S>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> S,
+) -> Result<Vec<N>, Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+{
+    let nodes = nodes.into_iter().collect::<Vec<_>>();
+    let mut edges = HashMap::new();
+    let mut predecessors: HashMap<N, Vec<N>> = HashMap::new();
+    let mut in_degree = nodes
+        .iter()
+        .map(|node| (node.clone(), 0))
+        .collect::<HashMap<_, _>>();
+    for node in &nodes {
+        let next = successors(node).into_iter().collect::<Vec<_>>();
+        for next in &next {
+            *in_degree.entry(next.clone()).or_insert(0) += 1;
+            predecessors
+                .entry(next.clone())
+                .or_default()
+                .push(node.clone());
+        }
+        edges.insert(node.clone(), next);
+    }
+    let mut ready = nodes
+        .iter()
+        .filter(|node| in_degree[*node] == 0)
+        .cloned()
+        .collect::<VecDeque<_>>();
+    let mut order = Vec::new();
+    while let Some(node) = ready.pop_front() {
+        for next in edges.get(&node).into_iter().flatten() {
+            let degree = in_degree.get_mut(next).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                ready.push_back(next.clone());
+            }
+        }
+        order.push(node);
+    }
+    let Some(start) = nodes.iter().find(|node| in_degree[*node] > 0) else {
+        return Ok(order);
+    };
+    // every node left unordered has a predecessor which is also unordered, so walking back through
+    // them must eventually repeat a node, which closes a cycle
+    let mut path = vec![start.clone()];
+    loop {
+        let node = &path[path.len() - 1];
+        let previous = predecessors[node]
+            .iter()
+            .find(|previous| in_degree[*previous] > 0)
+            .expect("an unordered node always has an unordered predecessor")
+            .clone();
+        if let Some(repeat) = path.iter().position(|seen| *seen == previous) {
+            let mut cycle = path.split_off(repeat);
+            cycle.reverse();
+            let first = (0..cycle.len())
+                .min_by_key(|&i| nodes.iter().position(|node| *node == cycle[i]))
+                .unwrap_or(0);
+            cycle.rotate_left(first);
+            return Err(cycle);
+        }
+        path.push(previous);
+    }
+}
+
+/// Groups nodes which can reach each other, `neighbours` must be symmetric
+pub fn connected_components<N, S>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> S,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component = flood_fill(node, &mut neighbours)
+            .into_iter()
+            .collect::<Vec<_>>();
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small weighted graph, `a` to `e` is cheapest through `c` but shortest through `b`
+    fn example() -> Graph<&'static str, u32> {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b", 5);
+        graph.add_edge("a", "c", 1);
+        graph.add_edge("b", "e", 5);
+        graph.add_edge("c", "d", 1);
+        graph.add_edge("d", "e", 1);
+        graph.add_edge("f", "g", 1);
+        graph
+    }
+
+    #[test]
+    fn search_test() {
+        let graph = example();
+        let id = |node| graph.id(&node).unwrap();
+        let names = |path: Vec<NodeId>| {
+            path.into_iter()
+                .map(|id| *graph.node(id))
+                .collect::<Vec<_>>()
+        };
+        let successors = |&node: &NodeId| graph.successors(node).collect::<Vec<_>>();
+        let e = id("e");
+
+        assert_eq!(
+            bfs(id("a"), successors, |&node| node == e).map(names),
+            Some(vec!["a", "b", "e"])
+        );
+        assert_eq!(bfs(id("e"), successors, |&node| node == id("a")), None);
+        assert_eq!(bfs_distances(id("a"), successors)[&e], 2);
+        assert_eq!(names(dfs(id("a"), successors)), ["a", "b", "e", "c", "d"]);
+        assert_eq!(flood_fill(id("c"), successors).len(), 3);
+
+        let weighted = |&node: &NodeId| graph.edges(node).collect::<Vec<_>>();
+        let (path, cost) = dijkstra(id("a"), weighted, |&node| node == e).unwrap();
+        assert_eq!((names(path), cost), (vec!["a", "c", "d", "e"], 3));
+        let (path, cost) = astar(id("a"), weighted, |_| 0, |&node| node == e).unwrap();
+        assert_eq!((names(path), cost), (vec!["a", "c", "d", "e"], 3));
+    }
+
+    #[test]
+    fn astar_grid_test() {
+        // a wall along x = 2 from y = 0 to 3, forcing a path around it
+        let wall = |(x, y): (i32, i32)| x == 2 && y < 4;
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y) && !wall((x, y)))
+                .map(|position| (position, 1))
+                .collect::<Vec<_>>()
+        };
+        let heuristic = |&(x, y): &(i32, i32)| (4 - x).abs() + y.abs();
+        let (path, cost) = astar((0, 0), successors, heuristic, |&p| p == (4, 0)).unwrap();
+        assert_eq!(cost, 12);
+        assert_eq!(path.len(), 13);
+    }
+
+    #[test]
+    fn cycle_test() {
+        // 4 hangs off the cycle between 2 and 3, so is left unordered without being on the cycle
+        let edges = HashMap::from([(1, vec![2]), (2, vec![3]), (3, vec![2, 4]), (4, vec![])]);
+        let successors = |node: &i32| edges[node].clone();
+        assert_eq!(topological_sort([4, 3, 2, 1], successors), Err(vec![3, 2]));
+        assert_eq!(topological_sort([1, 2, 3, 4], successors), Err(vec![2, 3]));
+        let acyclic = |node: &i32| {
+            let next = edges[node].iter().copied();
+            next.filter(|next| next > node).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            topological_sort([4, 3, 2, 1], acyclic),
+            Ok(vec![1, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn structure_test() {
+        let graph = example();
+        let names = |ids: &[NodeId]| ids.iter().map(|&id| *graph.node(id)).collect::<Vec<_>>();
+        let order = graph.topological_sort().unwrap();
+        let position = |node| names(&order).iter().position(|&n| n == node).unwrap();
+        assert!(position("a") < position("c"));
+        assert!(position("c") < position("d"));
+        assert!(position("d") < position("e"));
+        assert!(position("b") < position("e"));
+
+        let mut components = graph
+            .connected_components()
+            .iter()
+            .map(|component| {
+                let mut names = names(component);
+                names.sort();
+                names
+            })
+            .collect::<Vec<_>>();
+        components.sort();
+        assert_eq!(components, [vec!["a", "b", "c", "d", "e"], vec!["f", "g"]]);
+
+        let mut cyclic = graph.clone();
+        cyclic.add_edge("e", "a", 1);
+        let cycle = cyclic.topological_sort().unwrap_err();
+        assert_eq!(names(&cycle), ["a", "b", "e"]);
+        assert_eq!(
+            cyclic
+                .reversed()
+                .successors(cyclic.id(&"a").unwrap())
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! A two dimensional grid, used by the many advent of code problems set on a map
+use crate::{parsers, ParseResult};
+use ndarray::{s, Array2, ArrayView1, ArrayViewMut2};
+use nom::{error::VerboseError, Parser};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// A position within a grid, as `(row, column)`
+pub type Position = (usize, usize);
+
+/// Offsets to the four orthogonal neighbours of a position
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets to all eight neighbours of a position, including diagonals
+const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(cells: Array2<T>) -> Grid<T> {
+        Grid { cells }
+    }
+
+    pub fn from_elem(height: usize, width: usize, elem: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(Array2::from_elem((height, width), elem))
+    }
+
+    /// Parses lines of cells into a grid, every row must be the same length
+    pub fn parser<'a>(
+        cell: impl Parser<&'a str, T, VerboseError<&'a str>>,
+    ) -> impl FnMut(&'a str) -> ParseResult<'a, Grid<T>> {
+        nom::combinator::map(parsers::grid(cell), Grid::new)
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    pub fn cells(&self) -> &Array2<T> {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut Array2<T> {
+        &mut self.cells
+    }
+
+    /// The rectangle of cells between two corners, both of which are included
+    pub fn region_mut(&mut self, from: Position, to: Position) -> ArrayViewMut2<'_, T> {
+        self.cells.slice_mut(s![from.0..=to.0, from.1..=to.1])
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.cells.get(position)
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.cells.get_mut(position)
+    }
+
+    /// Gets a cell as if the grid repeated infinitely in every direction, or `None` if it is empty
+    pub fn get_wrapping(&self, (row, column): (isize, isize)) -> Option<&T> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        self.cells.get((
+            row.rem_euclid(self.height() as isize) as usize,
+            column.rem_euclid(self.width() as isize) as usize,
+        ))
+    }
+
+    /// Offsets a position, if the result is still within the grid
+    pub fn offset(&self, (row, column): Position, (dr, dc): (isize, isize)) -> Option<Position> {
+        let row = row
+            .checked_add_signed(dr)
+            .filter(|&row| row < self.height())?;
+        let column = column
+            .checked_add_signed(dc)
+            .filter(|&column| column < self.width())?;
+        Some((row, column))
+    }
+
+    /// The up to four orthogonal neighbours of a position which are within the grid
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// The up to eight neighbours of a position, including diagonals, which are within the grid
+    pub fn adjacent(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ADJACENT
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// Every position in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width();
+        (0..self.height()).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    /// Every cell in the grid alongside its position, row by row
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells.indexed_iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = ArrayView1<'_, T>> {
+        self.cells.rows().into_iter()
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = ArrayView1<'_, T>> {
+        self.cells.columns().into_iter()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.cells.map(f))
+    }
+
+    /// Displays the grid with each cell drawn as a single character
+    pub fn display<'a>(&'a self, draw: impl Fn(&T) -> char + 'a) -> impl fmt::Display + 'a {
+        DisplayGrid { grid: self, draw }
+    }
+}
+
+impl Grid<char> {
+    /// Parses a character map, where every character other than a line ending is a cell
+    pub fn parse_chars(input: &str) -> ParseResult<'_, Grid<char>> {
+        use nom::character::complete::none_of;
+        Grid::parser(none_of("\r\n"))(input)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+    fn index(&self, position: Position) -> &T {
+        &self.cells[position]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        &mut self.cells[position]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+struct DisplayGrid<'a, T, F> {
+    grid: &'a Grid<T>,
+    draw: F,
+}
+
+impl<T, F: Fn(&T) -> char> fmt::Display for DisplayGrid<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.grid.rows() {
+            for cell in row {
+                write!(f, "{}", (self.draw)(cell))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_all, parsers::digit};
+
+    #[test]
+    fn neighbour_test() {
+        let grid = Grid::from_elem(3, 4, 0);
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours((1, 1)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0), (1, 2), (2, 1)]
+        );
+        assert_eq!(
+            grid.adjacent((2, 3)).collect::<Vec<_>>(),
+            [(1, 2), (1, 3), (2, 2)]
+        );
+        assert_eq!(grid.adjacent((1, 1)).count(), 8);
+        assert_eq!(grid.offset((2, 3), (1, 0)), None);
+        assert_eq!(grid.positions().count(), 12);
+    }
+
+    #[test]
+    fn index_test() {
+        let input = "123\n456\n";
+        let grid = parse_all(input, Grid::parser(digit)(input)).unwrap();
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get_wrapping((2, 4)), Some(&2));
+        assert_eq!(grid.get_wrapping((-1, -1)), Some(&6));
+        assert_eq!(Grid::from_elem(0, 3, 0).get_wrapping((1, 1)), None);
+        assert_eq!(
+            grid.rows().map(|row| row.sum()).collect::<Vec<_>>(),
+            [6, 15]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.sum())
+                .collect::<Vec<_>>(),
+            [5, 7, 9]
+        );
+        assert_eq!(grid.to_string(), input);
+        assert_eq!(
+            grid.display(|&n| if n % 2 == 0 { '#' } else { '.' })
+                .to_string(),
+            ".#.\n#.#\n"
+        );
+
+        let mut grid = grid;
+        grid.region_mut((0, 1), (1, 2)).fill(0);
+        assert_eq!(grid.to_string(), "100\n400\n");
+    }
+
+    #[test]
+    fn char_map_test() {
+        let input = "#.\n.#";
+        let grid = parse_all(input, Grid::parse_chars(input)).unwrap();
+        assert_eq!(grid[(1, 1)], '#');
+        assert!(!grid.map(|&c| c == '#')[(0, 1)]);
+    }
+}
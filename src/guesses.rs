@@ -0,0 +1,155 @@
+//! A ledger of the answers submitted for each day, kept beside the inputs in
+//! `inputs/y####d##.guesses.toml`, so that no answer is ever submitted twice, and no numeric answer
+//! is submitted outside the bounds set by the "too high" and "too low" answers before it
+use crate::{client::Verdict, Part, Result};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Guess {
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// When the guess was submitted, in RFC 3339 format
+    pub submitted: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Guesses {
+    #[serde(default, rename = "guess")]
+    pub guesses: Vec<Guess>,
+}
+
+impl Guesses {
+    pub fn path(year: i32, day: u32) -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("inputs");
+        path.push(format!("y{:04}d{:02}.guesses.toml", year, day));
+        path
+    }
+
+    /// Loads the guesses made for a day, which are empty if none have been recorded
+    pub fn load(path: impl AsRef<Path>) -> Result<Guesses> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Guesses::default());
+        }
+        Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// The guesses made for one part
+    pub fn part(&self, part: Part) -> impl Iterator<Item = &Guess> {
+        self.guesses.iter().filter(move |guess| guess.part == part)
+    }
+
+    /// The range a numeric answer must be within, as the lowest answer which was too high and the
+    /// highest answer which was too low, both of which are excluded
+    pub fn bounds(&self, part: Part) -> (Option<i128>, Option<i128>) {
+        let numeric = |verdict| {
+            self.part(part)
+                .filter(move |guess| guess.verdict == verdict)
+                .filter_map(|guess| guess.answer.trim().parse::<i128>().ok())
+        };
+        (
+            numeric(Verdict::TooLow).max(),
+            numeric(Verdict::TooHigh).min(),
+        )
+    }
+
+    /// Errors if submitting an answer could not tell us anything new
+    pub fn check(&self, part: Part, answer: &str) -> Result<()> {
+        if let Some(correct) = self.part(part).find(|g| g.verdict == Verdict::Correct) {
+            return Err(format!("Part {} was already solved with {}", part, correct.answer).into());
+        }
+        if let Some(guess) = self.part(part).find(|guess| guess.answer == answer) {
+            return Err(format!(
+                "{} was already submitted for part {}, and was {}",
+                answer, part, guess.verdict
+            )
+            .into());
+        }
+        if let Ok(number) = answer.trim().parse::<i128>() {
+            let (too_low, too_high) = self.bounds(part);
+            if let Some(too_low) = too_low.filter(|&too_low| number <= too_low) {
+                return Err(
+                    format!("{} is not above {}, which was too low", answer, too_low).into(),
+                );
+            }
+            if let Some(too_high) = too_high.filter(|&too_high| number >= too_high) {
+                return Err(
+                    format!("{} is not below {}, which was too high", answer, too_high).into(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the verdict on a guess, unless the guess was turned away without being checked
+    pub fn record(&mut self, part: Part, answer: &str, verdict: Verdict) {
+        if verdict.is_judgement() {
+            self.guesses.push(Guess {
+                part,
+                answer: answer.to_string(),
+                verdict,
+                submitted: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ledger_test() {
+        let mut guesses = Guesses::default();
+        guesses.record(Part::One, "100", Verdict::TooHigh);
+        guesses.record(Part::One, "50", Verdict::Wait(30));
+        guesses.record(Part::Two, "7", Verdict::Correct);
+        assert_eq!(guesses.guesses.len(), 2);
+
+        assert!(guesses.check(Part::One, "50").is_ok());
+        assert_eq!(
+            guesses.check(Part::One, "100").unwrap_err().to_string(),
+            "100 was already submitted for part 1, and was too high"
+        );
+        assert_eq!(
+            guesses.check(Part::Two, "8").unwrap_err().to_string(),
+            "Part 2 was already solved with 7"
+        );
+
+        let saved = toml::to_string(&guesses).unwrap();
+        assert!(
+            saved.starts_with("[[guess]]\npart = 1\nanswer = \"100\"\nverdict = \"too-high\"\n")
+        );
+        assert_eq!(toml::from_str::<Guesses>(&saved).unwrap(), guesses);
+
+        guesses.record(Part::One, "20", Verdict::TooLow);
+        guesses.record(Part::One, "90", Verdict::TooHigh);
+        guesses.record(Part::One, "word", Verdict::Wrong);
+        assert_eq!(guesses.bounds(Part::One), (Some(20), Some(90)));
+        assert_eq!(guesses.bounds(Part::Two), (None, None));
+        assert!(guesses.check(Part::One, "89").is_ok());
+        assert!(guesses.check(Part::One, "other").is_ok());
+        assert_eq!(
+            guesses.check(Part::One, "95").unwrap_err().to_string(),
+            "95 is not below 90, which was too high"
+        );
+        assert_eq!(
+            guesses.check(Part::One, "-5").unwrap_err().to_string(),
+            "-5 is not above 20, which was too low"
+        );
+        let saved = toml::to_string(&guesses).unwrap();
+        assert_eq!(toml::from_str::<Guesses>(&saved).unwrap(), guesses);
+        assert_eq!(toml::from_str::<Guesses>("").unwrap(), Guesses::default());
+    }
+}
@@ -0,0 +1,331 @@
+//! Private leaderboards, fetched from the website's JSON API and cached in `target/leaderboards/`
+//!
+//! The website asks that the API is requested no more than once every 15 minutes, so a cached
+//! leaderboard is used until it is that old.
+use crate::{calendar, client::Client, Result};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// How long a leaderboard is cached before it is fetched again
+pub const CACHE_TIME: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Leaderboard {
+    /// The year of the event, which the website gives as a string
+    #[serde(rename = "event", deserialize_with = "deserialize_year")]
+    pub year: i32,
+    /// Every member of the leaderboard, by their id
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Missing for members who have not set a name
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// When the last star was collected, as a unix timestamp
+    #[serde(default)]
+    pub last_star_ts: i64,
+    /// The stars collected, by day and then by part
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub struct Star {
+    /// When the star was collected, as a unix timestamp
+    pub get_star_ts: i64,
+}
+
+fn deserialize_year<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<i32, D::Error> {
+    let event: String = serde::Deserialize::deserialize(deserializer)?;
+    event
+        .parse()
+        .map_err(|_| serde::de::Error::custom(format!("{} is not a year", event)))
+}
+
+pub fn cache_path(year: i32, id: u64) -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("target");
+    path.push("leaderboards");
+    path.push(format!("{:04}-{}.json", year, id));
+    path
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Leaderboard> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Loads a leaderboard from the cache, fetching it again once the cache is older than
+    /// [`CACHE_TIME`]
+    pub async fn load(client: &Client, year: i32, id: u64, cache: &Path) -> Result<Leaderboard> {
+        let fresh = fs::metadata(cache)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age < CACHE_TIME);
+        if fresh {
+            let leaderboard = Leaderboard::parse(&fs::read_to_string(cache)?)?;
+            if leaderboard.year == year {
+                return Ok(leaderboard);
+            }
+        }
+        let json = client.leaderboard(year, id).await?;
+        // only cached once it is known to be a leaderboard for the year
+        let leaderboard = Leaderboard::parse(&json)?;
+        if leaderboard.year != year {
+            return Err(format!(
+                "Fetched the leaderboard for {}, not {}",
+                leaderboard.year, year
+            )
+            .into());
+        }
+        if let Some(parent) = cache.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(cache, json)?;
+        Ok(leaderboard)
+    }
+
+    /// The members, ordered as the website ranks them
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|member| {
+            (
+                std::cmp::Reverse(member.local_score),
+                std::cmp::Reverse(member.stars),
+                member.last_star_ts,
+                member.id,
+            )
+        });
+        members
+    }
+}
+
+impl Member {
+    /// The name shown for the member, as the website shows members without one
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// How long after a day unlocked a part of it was solved, if it has been
+    pub fn completion_time(&self, year: i32, day: u32, part: u8) -> Option<Duration> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        let unlock = calendar::unlock_time(year, day)?;
+        let seconds = star.get_star_ts - unlock.timestamp();
+        u64::try_from(seconds).ok().map(Duration::from_secs)
+    }
+
+    /// The stars for each day, `*` for both parts, `+` for only the first, and `.` for neither
+    fn star_row(&self) -> String {
+        (1..=25)
+            .map(
+                |day| match self.completion_day_level.get(&day).map(BTreeMap::len) {
+                    Some(2) => '*',
+                    Some(1) => '+',
+                    _ => '.',
+                },
+            )
+            .collect()
+    }
+}
+
+/// Formats a completion time as `H:MM:SS`, with the days in front once it is a day or more
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (days, hours) = (seconds / 86_400, seconds / 3600 % 24);
+    let (minutes, seconds) = (seconds / 60 % 60, seconds % 60);
+    if days > 0 {
+        format!("{}d {:02}:{:02}:{:02}", days, hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    }
+}
+
+/// Displays as a table of the ranked members, followed by a table of how long each member took to
+/// solve each day
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranking = self.ranking();
+        let name_width = ranking
+            .iter()
+            .map(|member| member.display_name().chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Member".len());
+
+        writeln!(f, "Leaderboard {}", self.year)?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>4} {:>5} {:>5}  {:<25}  Member",
+            "Rank", "Score", "Stars", "Days"
+        )?;
+        writeln!(
+            f,
+            "{:>4} {:>5} {:>5}  {:<25}",
+            "", "", "", "1234567890123456789012345"
+        )?;
+        for (rank, member) in ranking.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4} {:>5} {:>5}  {}  {}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                member.star_row(),
+                member.display_name()
+            )?;
+        }
+
+        let days = (1..=25).filter(|day| {
+            ranking
+                .iter()
+                .any(|member| member.completion_day_level.contains_key(day))
+        });
+        for day in days {
+            let mut solvers: Vec<_> = ranking
+                .iter()
+                .filter_map(|member| {
+                    let part1 = member.completion_time(self.year, day, 1)?;
+                    Some((member, part1, member.completion_time(self.year, day, 2)))
+                })
+                .collect();
+            // fastest to finish the day first, then those who only finished the first part
+            solvers.sort_by_key(|&(_, part1, part2)| (part2.is_none(), part2, part1));
+            writeln!(f)?;
+            writeln!(
+                f,
+                "Day {:<2} {:<name_width$}  {:>12}  {:>12}",
+                day,
+                "Member",
+                "Part 1",
+                "Part 2",
+                name_width = name_width
+            )?;
+            for (member, part1, part2) in solvers {
+                let row = format!(
+                    "{:<6} {:<name_width$}  {:>12}  {:>12}",
+                    "",
+                    member.display_name(),
+                    format_duration(part1),
+                    part2.map(format_duration).unwrap_or_default(),
+                    name_width = name_width
+                );
+                writeln!(f, "{}", row.trim_end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::{stub_client, stub_server};
+
+    // 2021-12-01T05:00:00Z is 1638334800
+    const LEADERBOARD: &str = r#"{
+        "event": "2021",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "stars": 3, "local_score": 7, "global_score": 0,
+                "last_star_ts": 1638424800,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1638335100, "star_index": 0},
+                        "2": {"get_star_ts": 1638335700, "star_index": 1}
+                    },
+                    "2": {"1": {"get_star_ts": 1638424800, "star_index": 2}}
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 4, "global_score": 0,
+                "last_star_ts": 1638430000,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1638334900, "star_index": 3},
+                        "2": {"get_star_ts": 1638430000, "star_index": 4}
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": "Carol", "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": 0, "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn table_test() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let expected = "Leaderboard 2021
+
+Rank Score Stars  Days                       Member
+                  1234567890123456789012345
+   1     7     3  *+.......................  Alice
+   2     4     2  *........................  (anonymous user #2)
+   3     0     0  .........................  Carol
+
+Day 1  Member                     Part 1        Part 2
+       Alice                     0:05:00       0:15:00
+       (anonymous user #2)       0:01:40   1d 02:26:40
+
+Day 2  Member                     Part 1        Part 2
+       Alice                     1:00:00
+";
+        assert_eq!(leaderboard.to_string(), expected);
+        assert_eq!(
+            Leaderboard::parse(r#"{"event": "twenty", "members": {}}"#)
+                .unwrap_err()
+                .to_string(),
+            "twenty is not a year at line 1 column 18"
+        );
+    }
+
+    #[tokio::test]
+    async fn cache_test() {
+        let (url, requests) = stub_server(vec![(200, LEADERBOARD)]);
+        let client = stub_client(&url, 0.0);
+        let cache =
+            std::env::temp_dir().join(format!("aoc-leaderboard-{}.json", std::process::id()));
+        let _ = fs::remove_file(&cache);
+
+        let leaderboard = Leaderboard::load(&client, 2021, 1234, &cache)
+            .await
+            .unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2021/leaderboard/private/view/1234.json HTTP/1.1\r\n"));
+        // the stub only answers once, so this must come from the cache
+        let cached = Leaderboard::load(&client, 2021, 1234, &cache)
+            .await
+            .unwrap();
+        assert_eq!(cached, leaderboard);
+
+        // a stale cache is fetched again
+        let stale = std::time::SystemTime::now() - CACHE_TIME;
+        fs::File::options()
+            .write(true)
+            .open(&cache)
+            .unwrap()
+            .set_modified(stale)
+            .unwrap();
+        assert!(Leaderboard::load(&client, 2021, 1234, &cache)
+            .await
+            .is_err());
+        fs::remove_file(&cache).unwrap();
+    }
+}
@@ -1,109 +1,680 @@
 //! Common functionality, for advent of code solutions
-pub type ParseResult<'a, T> = nom::IResult<&'a str, T>;
+pub type ParseResult<'a, T> = nom::IResult<&'a str, T, nom::error::VerboseError<&'a str>>;
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
+// Allows solution modules to `use aoc::*` as they would from a binary
+extern crate self as aoc;
+
+pub mod calendar;
+pub mod client;
+pub mod dot;
+pub mod graph;
+pub mod grid;
+pub mod guesses;
+pub mod leaderboard;
+pub mod parsers;
+pub mod puzzle;
+pub mod registry;
+pub mod tsp;
+pub mod vm;
+
 #[derive(Debug, Clone, structopt::StructOpt)]
+#[structopt(name = "Advent Of Code")]
 pub struct Args {
-    #[structopt(parse(from_os_str))]
-    input_file: std::path::PathBuf,
+    #[structopt(short, long)]
+    pub year: i32,
+    #[structopt(short, long)]
+    pub day: u32,
+    /// The input file, or `-` to read the input from stdin
+    #[structopt(parse(from_os_str), required_unless = "input-str")]
+    input_file: Option<std::path::PathBuf>,
+    /// The input itself, such as an example from the puzzle text, instead of an input file
+    #[structopt(long, conflicts_with = "input-file")]
+    input_str: Option<String>,
+    /// Compare the answers against those recorded in `answers/`, failing on a mismatch
+    #[structopt(long)]
+    pub check: bool,
+    /// Only run the given part, either `1` or `2`
+    #[structopt(long)]
+    pub part: Option<Part>,
+    /// Output format, either `text` or `json`
+    #[structopt(long, default_value = "text")]
+    pub format: Format,
+    /// Also write the graph described by the input to a Graphviz DOT file, for days which have one
+    #[structopt(long, parse(from_os_str))]
+    pub dot: Option<std::path::PathBuf>,
+    /// Also write out the state worked out from the input, such as the signal on every wire, for
+    /// days which have one
+    #[structopt(long, parse(from_os_str))]
+    pub dump: Option<std::path::PathBuf>,
 }
 impl Args {
     pub fn input(&self) -> Result<String> {
-        Ok(std::fs::read_to_string(&self.input_file)?)
+        match (&self.input_str, &self.input_file) {
+            (Some(input), _) => Ok(input.clone()),
+            (None, Some(path)) if path.as_os_str() == "-" => {
+                let mut input = String::new();
+                std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)?;
+                Ok(input)
+            }
+            (None, Some(path)) => Ok(std::fs::read_to_string(path)?),
+            (None, None) => Err("No input provided".into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+impl std::str::FromStr for Format {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format {}, expected text or json", s)),
+        }
+    }
+}
+
+/// One of the two parts of a day, serialized as `1` or `2`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum Part {
+    One,
+    Two,
+}
+impl Part {
+    /// Whether this part should be run, given the part selected, if any
+    pub fn selected_by(self, selection: Option<Part>) -> bool {
+        selection.is_none_or(|selection| selection == self)
+    }
+}
+impl std::str::FromStr for Part {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Unknown part {}, expected 1 or 2", s)),
+        }
+    }
+}
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", u8::from(*self))
+    }
+}
+impl From<Part> for u8 {
+    fn from(part: Part) -> u8 {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+impl TryFrom<u8> for Part {
+    type Error = String;
+    fn try_from(part: u8) -> std::result::Result<Part, String> {
+        part.to_string().parse()
+    }
+}
+
+/// The debug formatted answers to the tasks of a day which were run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub task1: Option<String>,
+    pub task2: Option<String>,
+    pub timings: Timings,
+}
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(task1) = &self.task1 {
+            writeln!(f, "Task 1:\n{}", task1)?;
+        }
+        if let Some(task2) = &self.task2 {
+            writeln!(f, "Task 2:\n{}", task2)?;
+        }
+        write!(f, "{}", self.timings)
+    }
+}
+
+/// How long each phase of a solution took to run, serialized as seconds
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct Timings {
+    #[serde(serialize_with = "serialize_secs")]
+    pub parse: std::time::Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub task1: std::time::Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub task2: std::time::Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub total: std::time::Duration,
+}
+impl std::fmt::Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Parse: {:.2?}, Task 1: {:.2?}, Task 2: {:.2?}, Total: {:.2?}",
+            self.parse, self.task1, self.task2, self.total
+        )
+    }
+}
+
+fn serialize_secs<S: serde::Serializer>(
+    duration: &std::time::Duration,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+/// The machine readable outcome of running a solution, as output by `--format json`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Report {
+    pub year: i32,
+    pub day: u32,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub timings: Option<Timings>,
+    pub error: Option<String>,
+}
+impl Report {
+    pub fn new(year: i32, day: u32, answers: &Result<Answers>) -> Report {
+        match answers {
+            Ok(answers) => Report {
+                year,
+                day,
+                part1: answers.task1.clone(),
+                part2: answers.task2.clone(),
+                timings: Some(answers.timings),
+                error: None,
+            },
+            Err(e) => Report {
+                year,
+                day,
+                part1: None,
+                part2: None,
+                timings: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+/// Finishes parsing a whole input, failing if anything other than whitespace was left unparsed
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    parsed: ParseResult<'a, T>,
+) -> std::result::Result<T, ParseError> {
+    match nom::Finish::finish(parsed) {
+        Ok((remaining, _)) if !remaining.trim().is_empty() => {
+            Err(ParseError::trailing(input, remaining))
+        }
+        Ok((_, parsed)) => Ok(parsed),
+        Err(error) => Err(ParseError::new(input, error)),
+    }
+}
+
+/// Wraps a parser which is expected to leave input unparsed, opting it out of [`parse_all`]
+pub fn ignore_trailing<'a, T>(
+    mut parser: impl nom::Parser<&'a str, T, nom::error::VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, T> {
+    move |input| {
+        let (_, parsed) = parser.parse(input)?;
+        Ok(("", parsed))
+    }
+}
+
+/// A failure to parse an input, located by line and column
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The line the error occured on, shortened to the area around the error if it is long
+    snippet: String,
+    /// The offset of the error within the snippet, in characters
+    caret: usize,
+    /// What was expected where parsing failed, followed by each context it failed within
+    expected: Vec<String>,
+}
+impl ParseError {
+    const SNIPPET_RADIUS: usize = 40;
+
+    pub fn new(input: &str, error: nom::error::VerboseError<&str>) -> ParseError {
+        use nom::error::VerboseErrorKind;
+        let remaining = error.errors.first().map(|(i, _)| *i).unwrap_or_default();
+        let expected = error
+            .errors
+            .iter()
+            .enumerate()
+            .map(|(i, (_, kind))| match kind {
+                VerboseErrorKind::Context(context) => format!("in {}", context),
+                VerboseErrorKind::Char(c) => format!("expected {:?}", c),
+                VerboseErrorKind::Nom(kind) if i == 0 => format!("expected {}", kind.description()),
+                VerboseErrorKind::Nom(kind) => format!("in {}", kind.description()),
+            })
+            .collect();
+        ParseError::at(input, remaining, expected)
+    }
+
+    /// An error for input left over after a successful parse, `remaining` being where it stopped
+    pub fn trailing(input: &str, remaining: &str) -> ParseError {
+        ParseError::at(input, remaining, vec!["expected end of input".into()])
+    }
+
+    fn at(input: &str, remaining: &str, expected: Vec<String>) -> ParseError {
+        // errors can be raised on a slice taken from the input, rather than on what is left of it,
+        // so the offset is found from where `remaining` starts
+        let start = input.as_ptr() as usize;
+        let inside = (start..=start + input.len()).contains(&(remaining.as_ptr() as usize));
+        let mut offset = if inside {
+            nom::Offset::offset(input, remaining)
+        } else {
+            0
+        };
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let line = input[..offset].matches('\n').count() + 1;
+        let column = input[line_start..offset].chars().count() + 1;
+
+        let chars = input[line_start..line_end]
+            .trim_end_matches('\r')
+            .chars()
+            .collect::<Vec<_>>();
+        let start = (column - 1).saturating_sub(ParseError::SNIPPET_RADIUS);
+        let end = chars.len().min(column - 1 + ParseError::SNIPPET_RADIUS);
+        let mut snippet = chars[start..end].iter().collect::<String>();
+        let mut caret = column - 1 - start;
+        if start > 0 {
+            snippet.insert(0, '…');
+            caret += 1;
+        }
+        if end < chars.len() {
+            snippet.push('…');
+        }
+
+        ParseError {
+            line,
+            column,
+            snippet,
+            caret,
+            expected,
+        }
+    }
+}
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = self.line.to_string().len();
+        writeln!(
+            f,
+            "Parse error at line {}, column {}",
+            self.line, self.column
+        )?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{:gutter$} | {:caret$}^", "", "", caret = self.caret)?;
+        for expected in &self.expected {
+            write!(f, "\n{}", expected)?;
+        }
+        Ok(())
+    }
+}
+// Debug shows the formatted error, as it is what is printed when returned from main
+impl std::fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+impl std::error::Error for ParseError {}
+
+/// The answers accepted by advent of code for a day, recorded in `answers/y####d##.toml`
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct AcceptedAnswers {
+    pub task1: Option<String>,
+    pub task2: Option<String>,
+}
+impl AcceptedAnswers {
+    pub fn path(year: i32, day: u32) -> std::path::PathBuf {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("answers");
+        path.push(format!("y{:04}d{:02}.toml", year, day));
+        path
     }
+
+    /// Loads the accepted answers for a day, if any have been recorded
+    pub fn load(year: i32, day: u32) -> Result<Option<AcceptedAnswers>> {
+        let path = AcceptedAnswers::path(year, day);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(toml::from_str(&std::fs::read_to_string(path)?)?))
+    }
+
+    /// Compares answers against those accepted, erroring with every task run that does not match
+    pub fn verify(&self, answers: &Answers) -> Result<()> {
+        let mismatches = [
+            ("Task 1", &self.task1, &answers.task1),
+            ("Task 2", &self.task2, &answers.task2),
+        ]
+        .into_iter()
+        .filter_map(|(task, accepted, answer)| match (accepted, answer) {
+            (Some(accepted), Some(answer)) if accepted != answer => {
+                Some(format!("{} expected {}, got {}", task, accepted, answer))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches.join("\n").into())
+        }
+    }
+}
+
+/// A registered solution, runnable against the input for its day
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    /// The name of the solution's module, which starts with its year and day, such as `y2015d01`
+    pub name: &'static str,
+    pub year: i32,
+    pub day: u32,
+    run: fn(&str, Option<Part>) -> Result<Answers>,
+    dot: Option<fn(&str) -> Result<dot::Dot>>,
+    dump: Option<fn(&str) -> Result<String>>,
+}
+impl Solution {
+    /// Runs the solution against an input, running only one part if one is given
+    pub fn run(&self, input: &str, part: Option<Part>) -> Result<Answers> {
+        (self.run)(input, part)
+    }
+
+    /// Exports the graph described by an input, for days registered with `: dot`
+    pub fn dot(&self, input: &str) -> Result<dot::Dot> {
+        let dot = self.dot.ok_or("No graph export for that day")?;
+        dot(input)
+    }
+
+    /// Describes the state worked out from an input, for days registered with `: dump`
+    pub fn dump(&self, input: &str) -> Result<String> {
+        let dump = self.dump.ok_or("No dump for that day")?;
+        dump(input)
+    }
+}
+
+/// Finds the registered solution for a given year and day
+pub fn solution(year: i32, day: u32) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|s| s.year == year && s.day == day)
 }
 
+/// Finds a registered solution by the name of its module, which may be one of several for a day
+pub fn solution_named(name: &str) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|s| s.name == name)
+}
+
+/// Reads the number between two offsets of a module name such as `y2015d01`
+const fn name_digits(name: &str, from: usize, to: usize) -> u32 {
+    let bytes = name.as_bytes();
+    let mut value = 0;
+    let mut i = from;
+    while i < to {
+        value = value * 10 + (bytes[i] - b'0') as u32;
+        i += 1;
+    }
+    value
+}
+
+/// Declares each solution module, and registers its `run` entry point into [`SOLUTIONS`], along
+/// with any of the `dot` and `dump` exports listed after it, such as `y2015d07: dot dump`
+macro_rules! solutions {
+    ($($name:ident $(: $($export:ident)+)?,)*) => {
+        $(mod $name;)*
+
+        /// Every solution in the repository, in year and day order
+        pub const SOLUTIONS: &[Solution] = &[$(Solution {
+            name: stringify!($name),
+            year: name_digits(stringify!($name), 1, 5) as i32,
+            day: name_digits(stringify!($name), 6, 8),
+            run: $name::run,
+            dot: solutions!(@export dot $name $($($export)+)?),
+            dump: solutions!(@export dump $name $($($export)+)?),
+        },)*];
+    };
+    (@export $export:ident $name:ident) => {
+        None
+    };
+    (@export dot $name:ident dot $($rest:ident)*) => {
+        Some($name::dot)
+    };
+    (@export dump $name:ident dump $($rest:ident)*) => {
+        Some($name::dump)
+    };
+    (@export $export:ident $name:ident $other:ident $($rest:ident)*) => {
+        solutions!(@export $export $name $($rest)*)
+    };
+}
+
+/// Generates the `run` entry point of a solution module, for registration in [`SOLUTIONS`]
 #[macro_export]
 macro_rules! aoc_main {
     ($parse:ident, $task1:ident, $task2:ident) => {
-        fn main() -> Result<()> {
-            let input = aoc_main!(@input);
-            let parsed = $parse(&input);
-            let parsed = aoc_main!(@finalize, parsed);
-            let task1 = $task1(&parsed.1)?;
-            println!("Task 1:\n{:?}", task1);
-            let task2 = $task2(&parsed.1)?;
-            println!("Task 2:\n{:?}", task2);
-            Ok(())
+        pub fn run(
+            input: &str,
+            part: Option<$crate::Part>,
+        ) -> $crate::Result<$crate::Answers> {
+            let start = std::time::Instant::now();
+            let (parsed, parse) = aoc_main!(@time, aoc_main!(@parse, $parse, input));
+            let (task1, task1_time) = aoc_main!(@task, part, One, $task1(&parsed)?);
+            let (task2, task2_time) = aoc_main!(@task, part, Two, $task2(&parsed)?);
+            Ok($crate::Answers {
+                task1: task1.map(|task1| format!("{:?}", task1)),
+                task2: task2.map(|task2| format!("{:?}", task2)),
+                timings: $crate::Timings {
+                    parse,
+                    task1: task1_time,
+                    task2: task2_time,
+                    total: start.elapsed(),
+                },
+            })
         }
     };
     ($parse1:ident, $parse2:ident, $task1:ident, $task2:ident) => {
-        fn main() -> Result<()> {
-            let input = aoc_main!(@input);
-            let parsed1 = $parse1(&input);
-            let parsed1 = aoc_main!(@finalize, parsed1);
-            let task1 = $task1(&parsed1.1)?;
-            println!("Task 1:\n{:?}", task1);
-            let parsed2 = $parse2(&input);
-            let parsed2 = aoc_main!(@finalize, parsed2);
-            let task2 = $task2(&parsed2.1)?;
-            println!("Task 2:\n{:?}", task2);
-            Ok(())
+        pub fn run(
+            input: &str,
+            part: Option<$crate::Part>,
+        ) -> $crate::Result<$crate::Answers> {
+            let start = std::time::Instant::now();
+            let (parsed1, parse1) = aoc_main!(@task, part, One, aoc_main!(@parse, $parse1, input));
+            let (task1, task1_time) = match parsed1 {
+                Some(parsed1) => aoc_main!(@task, part, One, $task1(&parsed1)?),
+                None => (None, std::time::Duration::ZERO),
+            };
+            let (parsed2, parse2) = aoc_main!(@task, part, Two, aoc_main!(@parse, $parse2, input));
+            let (task2, task2_time) = match parsed2 {
+                Some(parsed2) => aoc_main!(@task, part, Two, $task2(&parsed2)?),
+                None => (None, std::time::Duration::ZERO),
+            };
+            Ok($crate::Answers {
+                task1: task1.map(|task1| format!("{:?}", task1)),
+                task2: task2.map(|task2| format!("{:?}", task2)),
+                timings: $crate::Timings {
+                    parse: parse1 + parse2,
+                    task1: task1_time,
+                    task2: task2_time,
+                    total: start.elapsed(),
+                },
+            })
         }
     };
     ($parse:ident, $task1:ident -> $task2:ident) => {
-        fn main() -> Result<()> {
-            let input = aoc_main!(@input);
-            let parsed = $parse(&input);
-            let parsed = aoc_main!(@finalize, parsed);
-            let task1 = $task1(&parsed.1)?;
-            println!("Task 1:\n{:?}", task1);
-            let task2 = $task2(&parsed.1, task1)?;
-            println!("Task 2:\n{:?}", task2);
-            Ok(())
+        pub fn run(
+            input: &str,
+            part: Option<$crate::Part>,
+        ) -> $crate::Result<$crate::Answers> {
+            let start = std::time::Instant::now();
+            let (parsed, parse) = aoc_main!(@time, aoc_main!(@parse, $parse, input));
+            // task 2 depends on the value of task 1, so it is always run
+            let (task1, task1_time) = aoc_main!(@time, $task1(&parsed)?);
+            let task1_answer = $crate::Part::One
+                .selected_by(part)
+                .then(|| format!("{:?}", task1));
+            let (task2, task2_time) = aoc_main!(@task, part, Two, $task2(&parsed, task1)?);
+            Ok($crate::Answers {
+                task1: task1_answer,
+                task2: task2.map(|task2| format!("{:?}", task2)),
+                timings: $crate::Timings {
+                    parse,
+                    task1: task1_time,
+                    task2: task2_time,
+                    total: start.elapsed(),
+                },
+            })
         }
     };
     ($parse1:ident, $parse2:ident, $task1:ident -> $task2:ident) => {
-        fn main() -> Result<()> {
-            let input = aoc_main!(@input);
-            let parsed1 = $parse1(&input);
-            let parsed1 = aoc_main!(@finalize, parsed1);
-            let task1 = $task1(&parsed1.1)?;
-            println!("Task 1:\n{:?}", task1);
-            let parsed2 = $parse2(&input);
-            let parsed2 = aoc_main(@finalize, parsed2);
-            let task2 = $task2(&parsed2.1, task1)?;
-            println!("Task 2:\n{:?}", task2);
-            Ok(())
+        pub fn run(
+            input: &str,
+            part: Option<$crate::Part>,
+        ) -> $crate::Result<$crate::Answers> {
+            let start = std::time::Instant::now();
+            let (parsed1, parse1) = aoc_main!(@time, aoc_main!(@parse, $parse1, input));
+            // task 2 depends on the value of task 1, so it is always run
+            let (task1, task1_time) = aoc_main!(@time, $task1(&parsed1)?);
+            let task1_answer = $crate::Part::One
+                .selected_by(part)
+                .then(|| format!("{:?}", task1));
+            let (parsed2, parse2) = aoc_main!(@task, part, Two, aoc_main!(@parse, $parse2, input));
+            let (task2, task2_time) = match parsed2 {
+                Some(parsed2) => aoc_main!(@task, part, Two, $task2(&parsed2, task1)?),
+                None => (None, std::time::Duration::ZERO),
+            };
+            Ok($crate::Answers {
+                task1: task1_answer,
+                task2: task2.map(|task2| format!("{:?}", task2)),
+                timings: $crate::Timings {
+                    parse: parse1 + parse2,
+                    task1: task1_time,
+                    task2: task2_time,
+                    total: start.elapsed(),
+                },
+            })
         }
     };
 
-    (@input) => {
-        <Args as structopt::StructOpt>::from_args().input()?
+    (@task, $part:expr, $which:ident, $run:expr) => {{
+        if $crate::Part::$which.selected_by($part) {
+            let (value, elapsed) = aoc_main!(@time, $run);
+            (Some(value), elapsed)
+        } else {
+            (None, std::time::Duration::ZERO)
+        }
+    }};
+
+    (@time, $run:expr) => {{
+        let start = std::time::Instant::now();
+        let value = $run;
+        (value, start.elapsed())
+    }};
+
+    (@parse, $parse:ident, $input:expr) => {
+        $crate::parse_all($input, $parse($input))?
     };
+}
 
-    (@finalize, $parsed:expr) => {
-        nom::Finish::finish($parsed).map_err(|nom::error::Error { input, code }| {
-            nom::error::Error {
-                input: input.to_string(),
-                code,
-            }
-        })?
-    }
+/// Generates the `dot` graph export of a solution module, registered by listing the module in
+/// [`solutions!`] as `yYYYYdDD: dot`
+#[macro_export]
+macro_rules! aoc_dot {
+    ($parse:ident, $export:ident) => {
+        pub fn dot(input: &str) -> $crate::Result<$crate::dot::Dot> {
+            let parsed = aoc_main!(@parse, $parse, input);
+            $export(&parsed)
+        }
+    };
+}
+
+/// Generates the `dump` of a solution module, which describes the state worked out from its input,
+/// registered by listing the module in [`solutions!`] as `yYYYYdDD: dump`
+#[macro_export]
+macro_rules! aoc_dump {
+    ($parse:ident, $describe:ident) => {
+        pub fn dump(input: &str) -> $crate::Result<String> {
+            let parsed = aoc_main!(@parse, $parse, input);
+            Ok($describe(&parsed)?.to_string())
+        }
+    };
 }
 
 #[macro_export]
 macro_rules! assert_task {
     ($parse:ident, $task:ident, $input:expr, $equals:expr) => {{
-        let input = $parse($input).unwrap().1;
+        let input = $crate::parse_all($input, $parse($input)).unwrap();
         let task = $task(&input).unwrap();
         assert_eq!(format!("{:?}", task), format!("{:?}", $equals))
     }};
     ($parse:ident, $task1:ident -> $task2:ident, $input:expr, $equals:expr) => {{
-        let input = $parse($input).unwrap().1;
+        let input = $crate::parse_all($input, $parse($input)).unwrap();
         let task1 = $task1(&input).unwrap();
         let task2 = $task2(&input, task1).unwrap();
         assert_eq!(format!("{:?}", task2), format!("{:?}", $equals))
     }};
     ($parse1:ident, $parse2:ident, $task1:ident -> $task2:ident, $input:expr, $equals:expr) => {{
-        let input1 = $parse1($input).unwrap().1;
+        let input1 = $crate::parse_all($input, $parse1($input)).unwrap();
         let task1 = $task1(&input1).unwrap();
-        let input2 = $parse2($input).unwrap().1;
+        let input2 = $crate::parse_all($input, $parse2($input)).unwrap();
         let task2 = $task2(&input2, task1).unwrap();
         assert_eq!(format!("{:?}", task2), format!("{:?}", $equals))
     }};
 }
 
+solutions! {
+    y2015d01,
+    y2015d02,
+    y2015d03,
+    y2015d04,
+    y2015d05,
+    y2015d06,
+    y2015d07: dot dump,
+    y2015d08,
+    y2015d09,
+    y2015d10,
+    y2015d11,
+    y2015d12,
+    y2015d13,
+    y2017d01,
+    y2020d01,
+    y2020d02,
+    y2020d03,
+    y2020d04,
+    y2020d05,
+    y2020d06,
+    y2020d07,
+    y2020d08,
+    y2020d09,
+    y2020d10,
+    y2021d01,
+    y2021d02,
+    y2021d03,
+    y2021d04,
+    y2021d05,
+    y2021d06,
+    y2021d07,
+    y2021d08,
+    y2021d09,
+    y2021d10,
+    y2021d11,
+    y2021d12: dot,
+    y2022d01,
+    y2022d02,
+    y2022d03,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,7 +684,7 @@ mod tests {
         struct ParsedInput<'a> {
             input: &'a str,
         }
-        fn parse(input: &str) -> ParseResult<ParsedInput<'_>> {
+        fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
             Ok(("", ParsedInput { input }))
         }
         fn task1(input: &ParsedInput) -> Result<usize> {
@@ -131,19 +702,22 @@ mod tests {
         struct ParsedInput<'a> {
             input: &'a str,
         }
-        fn parse(input: &str) -> ParseResult<ParsedInput<'_>> {
+        fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
             Ok(("", ParsedInput { input }))
         }
         fn task1(input: &ParsedInput) -> Result<usize> {
             Ok(input.input.len())
         }
-        fn alt_parse(input: &str) -> ParseResult<ParsedInput<'_>> {
+        fn two_chars(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
             if input.len() >= 2 {
                 Ok((&input[2..], ParsedInput { input: &input[..2] }))
             } else {
                 Ok(("", ParsedInput { input: "" }))
             }
         }
+        fn alt_parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
+            ignore_trailing(two_chars)(input)
+        }
         fn task2(input: &ParsedInput) -> Result<String> {
             Ok(input.input.len().to_string())
         }
@@ -155,4 +729,109 @@ mod tests {
         assert_task!(parse, task1 -> task2i, "184asd", 36);
         assert_task!(parse, alt_parse, task1 -> task2i, "184asd", 12);
     }
+
+    #[test]
+    fn parse_error_test() {
+        use nom::{
+            character::complete::{char, digit1, line_ending, u32},
+            combinator::opt,
+            error::context,
+            multi::count,
+            sequence::{separated_pair, terminated},
+            Finish,
+        };
+        fn parse(input: &str) -> ParseResult<'_, Vec<(u32, u32)>> {
+            let pair = context("pair", separated_pair(u32, char(','), u32));
+            count(terminated(pair, opt(line_ending)), 3)(input)
+        }
+        let input = "1,2\n3,4\n5;6\n";
+        let error = ParseError::new(input, parse(input).finish().unwrap_err());
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 3, column 2\n3 | 5;6\n  |  ^\nexpected ','\nin pair\nin Count"
+        );
+
+        let input = format!("{}x", "1".repeat(100));
+        let error = separated_pair(digit1, char(','), digit1)(input.as_str())
+            .finish()
+            .map(|_: (&str, (&str, &str))| ())
+            .unwrap_err();
+        let error = ParseError::new(&input, error);
+        assert_eq!((error.line, error.column), (1, 101));
+        assert_eq!(error.snippet, format!("…{}x", "1".repeat(40)));
+        assert_eq!(error.caret, 41);
+
+        // an error raised on a slice of the input is still placed where the slice starts
+        fn digits(input: &str) -> ParseResult<'_, Vec<u8>> {
+            use nom::{bytes::complete::take, character::complete::u8, combinator::map_parser};
+            nom::multi::many1(map_parser(take(1usize), u8))(input)
+        }
+        for input in ["a12", "aé"] {
+            let error = ParseError::new(input, digits(input).finish().unwrap_err());
+            assert_eq!((error.line, error.column), (1, 1));
+        }
+    }
+
+    #[test]
+    fn trailing_test() {
+        use nom::{character::complete::u32, Finish};
+        assert_eq!(parse_all("12\n", u32("12\n")), Ok(12));
+        assert_eq!(
+            parse_all("12\n34", u32("12\n34")).unwrap_err().to_string(),
+            "Parse error at line 1, column 3\n1 | 12\n  |   ^\nexpected end of input"
+        );
+        assert_eq!(parse_all("12\n34", ignore_trailing(u32)("12\n34")), Ok(12));
+        assert!(parse_all("x", u32("x")).is_err());
+        assert!(ignore_trailing(u32)("x").finish().is_err());
+    }
+
+    #[test]
+    fn report_test() {
+        let answers = Answers {
+            task1: Some("6".into()),
+            task2: None,
+            timings: Timings {
+                total: std::time::Duration::from_millis(1500),
+                ..Timings::default()
+            },
+        };
+        assert_eq!(
+            serde_json::to_string(&Report::new(2015, 1, &Ok(answers))).unwrap(),
+            r#"{"year":2015,"day":1,"part1":"6","part2":null,"timings":{"parse":0.0,"task1":0.0,"task2":0.0,"total":1.5},"error":null}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Report::new(2015, 1, &Err("oops".into()))).unwrap(),
+            r#"{"year":2015,"day":1,"part1":null,"part2":null,"timings":null,"error":"oops"}"#
+        );
+    }
+
+    #[test]
+    fn verify_test() {
+        let answers = Answers {
+            task1: Some("6".into()),
+            task2: Some("\"6\"".into()),
+            timings: Timings::default(),
+        };
+        let accepted = |task1: Option<&str>, task2: Option<&str>| AcceptedAnswers {
+            task1: task1.map(String::from),
+            task2: task2.map(String::from),
+        };
+        assert!(accepted(Some("6"), Some("\"6\"")).verify(&answers).is_ok());
+        assert!(accepted(Some("6"), None).verify(&answers).is_ok());
+        assert!(accepted(None, None).verify(&answers).is_ok());
+        let task2_only = Answers {
+            task1: None,
+            ..answers.clone()
+        };
+        assert!(accepted(Some("7"), Some("\"6\""))
+            .verify(&task2_only)
+            .is_ok());
+        assert_eq!(
+            accepted(Some("7"), Some("6"))
+                .verify(&answers)
+                .unwrap_err()
+                .to_string(),
+            "Task 1 expected 7, got 6\nTask 2 expected 6, got \"6\""
+        );
+    }
 }
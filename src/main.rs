@@ -0,0 +1,47 @@
+use aoc::*;
+use structopt::StructOpt;
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    let answers = solution(args.year, args.day)
+        .ok_or_else(|| "No solution registered for that day".into())
+        .and_then(|solution| {
+            let input = args.input()?;
+            if let Some(path) = &args.dot {
+                std::fs::write(path, solution.dot(&input)?.to_string())?;
+            }
+            if let Some(path) = &args.dump {
+                std::fs::write(path, solution.dump(&input)?)?;
+            }
+            solution.run(&input, args.part)
+        });
+    let checked = match &answers {
+        Ok(answers) if args.check => {
+            AcceptedAnswers::load(args.year, args.day).and_then(|accepted| {
+                accepted
+                    .ok_or("No accepted answers recorded for that day")?
+                    .verify(answers)
+            })
+        }
+        _ => Ok(()),
+    };
+    match args.format {
+        Format::Text => {
+            println!("{}", answers?);
+            checked?;
+            if args.check {
+                println!("Answers match those accepted");
+            }
+        }
+        Format::Json => {
+            let mut report = Report::new(args.year, args.day, &answers);
+            if let Err(e) = &checked {
+                report.error = Some(e.to_string());
+            }
+            println!("{}", serde_json::to_string(&report)?);
+            answers?;
+            checked?;
+        }
+    }
+    Ok(())
+}
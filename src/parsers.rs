@@ -1 +1,103 @@
 //! Common parser infrastructure, used by many different advent of code problems
+use crate::ParseResult;
+use ndarray::{Array1, Array2};
+use nom::{
+    character::complete::{char, line_ending, satisfy},
+    combinator::map_res,
+    error::VerboseError,
+    multi::{many1, separated_list1},
+    sequence::{pair, separated_pair},
+    Parser,
+};
+
+/// Parses one item per line
+pub fn lines<'a, O>(
+    item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>> {
+    separated_list1(line_ending, item)
+}
+
+/// Parses blocks of lines, each separated by a blank line
+pub fn blocks<'a, O>(
+    block: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>> {
+    separated_list1(pair(line_ending, line_ending), block)
+}
+
+/// Parses items separated by commas, such as `3,4,3,1,2`
+pub fn comma_separated<'a, O>(
+    item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>> {
+    separated_list1(char(','), item)
+}
+
+/// Parses a pair of coordinates separated by a comma, such as `0,9`
+pub fn coordinate<'a, O>(
+    axis: impl Parser<&'a str, O, VerboseError<&'a str>> + Clone,
+) -> impl FnMut(&'a str) -> ParseResult<'a, (O, O)> {
+    separated_pair(axis.clone(), char(','), axis)
+}
+
+/// Parses lines of cells into a grid indexed by `[row, column]`, every row must be the same length
+pub fn grid<'a, O>(
+    cell: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Array2<O>> {
+    map_res(lines(many1(cell)), |rows| {
+        let shape = (rows.len(), rows.first().map_or(0, Vec::len));
+        Array1::from_iter(rows.into_iter().flatten()).into_shape(shape)
+    })
+}
+
+/// Parses a single decimal digit as its value
+pub fn digit(input: &str) -> ParseResult<'_, u8> {
+    satisfy(|c| c.is_ascii_digit())
+        .map(|c| c as u8 - b'0')
+        .parse(input)
+}
+
+/// Parses a grid of single digit numbers, such as a height map
+pub fn digit_grid(input: &str) -> ParseResult<'_, Array2<u8>> {
+    grid(digit)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_all;
+    use ndarray::arr2;
+    use nom::character::complete::{alpha1, u32};
+
+    #[test]
+    fn list_test() {
+        assert_eq!(
+            parse_all("1\n2\n3\n", lines(u32)("1\n2\n3\n")),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            parse_all("3,4,3", comma_separated(u32)("3,4,3")),
+            Ok(vec![3, 4, 3])
+        );
+        assert_eq!(parse_all("0,9", coordinate(u32)("0,9")), Ok((0, 9)));
+        let input = "ab\nc\n\nd\n\ne\nf";
+        assert_eq!(
+            parse_all(input, blocks(lines(alpha1))(input)),
+            Ok(vec![vec!["ab", "c"], vec!["d"], vec!["e", "f"]])
+        );
+    }
+
+    #[test]
+    fn grid_test() {
+        let input = "123\n456\n";
+        assert_eq!(
+            parse_all(input, digit_grid(input)),
+            Ok(arr2(&[[1, 2, 3], [4, 5, 6]]))
+        );
+        let input = ".#\n#.";
+        let mut trees = grid(satisfy(|c| c == '.' || c == '#').map(|c| c == '#'));
+        assert_eq!(
+            parse_all(input, trees(input)),
+            Ok(arr2(&[[false, true], [true, false]]))
+        );
+        assert!(parse_all("12\n3", digit_grid("12\n3")).is_err());
+    }
+}
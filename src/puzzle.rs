@@ -0,0 +1,300 @@
+//! Puzzle descriptions, converted from the website's HTML into Markdown and kept in
+//! `puzzles/y####d##.md`, so the statement can be read offline beside the solution
+//!
+//! Only the small subset of HTML used by puzzle descriptions is understood: headings, paragraphs,
+//! emphasis, code, lists and links. Anything else is reduced to its text.
+use std::path::PathBuf;
+
+pub fn path(year: i32, day: u32) -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("puzzles");
+    path.push(format!("y{:04}d{:02}.md", year, day));
+    path
+}
+
+/// Whether a saved description includes the second part, which is only shown once the first part
+/// has been solved
+pub fn has_part_two(markdown: &str) -> bool {
+    markdown.contains("--- Part Two ---")
+}
+
+/// Converts every `<article>` of a puzzle page into Markdown, with relative links resolved against
+/// the website's base URL, or `None` if the page has no articles
+pub fn to_markdown(page: &str, base_url: &str) -> Option<String> {
+    let mut articles = Vec::new();
+    let mut rest = page;
+    while let Some(start) = rest.find("<article") {
+        let content = &rest[start..];
+        let content = &content[content.find('>')? + 1..];
+        let end = content.find("</article>")?;
+        articles.push(Converter::new(base_url).convert(&content[..end]));
+        rest = &content[end..];
+    }
+    (!articles.is_empty()).then(|| articles.join("\n\n") + "\n")
+}
+
+struct Converter<'a> {
+    base_url: &'a str,
+    output: String,
+    /// How many `<pre>` and `<code>` elements the converter is within
+    pre: usize,
+    code: usize,
+    /// The targets of the links the converter is within
+    links: Vec<String>,
+}
+
+impl<'a> Converter<'a> {
+    fn new(base_url: &'a str) -> Converter<'a> {
+        Converter {
+            base_url,
+            output: String::new(),
+            pre: 0,
+            code: 0,
+            links: Vec::new(),
+        }
+    }
+
+    fn convert(mut self, html: &str) -> String {
+        let mut rest = html;
+        while !rest.is_empty() {
+            match rest.find('<') {
+                Some(0) => {
+                    let opens_tag = rest[1..]
+                        .starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!');
+                    match rest.find('>') {
+                        Some(end) if opens_tag => {
+                            self.tag(&rest[1..end]);
+                            rest = &rest[end + 1..];
+                        }
+                        // a `<` which does not start a tag, or a tag which is never closed, is
+                        // kept as text, as a browser would
+                        _ => {
+                            self.text("<");
+                            rest = &rest[1..];
+                        }
+                    }
+                }
+                Some(start) => {
+                    self.text(&rest[..start]);
+                    rest = &rest[start..];
+                }
+                None => {
+                    self.text(rest);
+                    rest = "";
+                }
+            }
+        }
+        self.output.trim().to_string()
+    }
+
+    fn text(&mut self, text: &str) {
+        let text = decode_entities(text);
+        if self.pre > 0 {
+            self.output.push_str(&text);
+            return;
+        }
+        // outside of preformatted text, whitespace is collapsed as a browser would
+        for (i, word) in text.split(char::is_whitespace).enumerate() {
+            if i > 0 && !self.output.ends_with([' ', '\n']) && !self.output.is_empty() {
+                self.output.push(' ');
+            }
+            self.output.push_str(word);
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/');
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match (name.as_str(), closing) {
+            ("h2", false) => self.block("## "),
+            ("p", false) | ("ul", false) => self.block(""),
+            ("h2", true) | ("p", true) | ("ul", true) => self.block(""),
+            ("li", false) => {
+                self.line();
+                self.output.push_str("- ");
+            }
+            ("pre", false) => {
+                self.block("```\n");
+                self.pre += 1;
+            }
+            ("pre", true) if self.pre > 0 => {
+                self.pre -= 1;
+                self.line();
+                self.output.push_str("```");
+                self.block("");
+            }
+            ("code", false) if self.pre == 0 => {
+                self.code += 1;
+                self.output.push('`');
+            }
+            ("code", true) if self.pre == 0 && self.code > 0 => {
+                self.code -= 1;
+                self.output.push('`');
+            }
+            // emphasis cannot be shown within code
+            ("em", _) if self.pre == 0 && self.code == 0 => self.output.push('*'),
+            ("a", false) => {
+                let href = attribute(tag, "href").unwrap_or_default();
+                let href = if href.starts_with('/') {
+                    format!("{}{}", self.base_url, href)
+                } else {
+                    href
+                };
+                self.links.push(href);
+                self.output.push('[');
+            }
+            ("a", true) => {
+                let href = self.links.pop().unwrap_or_default();
+                self.output.push_str(&format!("]({})", href));
+            }
+            ("br", _) => self.output.push('\n'),
+            _ => {}
+        }
+    }
+
+    /// Ends the current line, if anything has been written to it
+    fn line(&mut self) {
+        let trimmed = self.output.trim_end_matches(' ').len();
+        self.output.truncate(trimmed);
+        if !self.output.is_empty() && !self.output.ends_with('\n') {
+            self.output.push('\n');
+        }
+    }
+
+    /// Starts a new block, separated from the previous block by a blank line
+    fn block(&mut self, prefix: &str) {
+        let trimmed = self.output.trim_end_matches([' ', '\n']).len();
+        self.output.truncate(trimmed);
+        if !self.output.is_empty() {
+            self.output.push_str("\n\n");
+        }
+        self.output.push_str(prefix);
+    }
+}
+
+/// Reads the value of an attribute from the inside of a tag, such as `a href="/2015"`
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+    let end = start + tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .filter(|&end| end <= 8)
+            .and_then(|end| Some((decode_entity(&rest[1..end])?, end)));
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let number = entity.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_test() {
+        let page = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Not Quite Lisp ---</h2><p>Santa is trying to deliver presents in a <em>large apartment building</em>, but he can't find the right floor.</p>
+<p>For example:</p>
+<ul>
+<li><code>(())</code> and <code>()()</code> both result in floor <code>0</code>.</li>
+<li><code>&gt;</code> is <code><em>not</em></code> a <a href="/2015/about" target="_blank">bracket</a>.</li>
+</ul>
+<pre><code>1 &lt; 2
+  3 &amp;&amp; 4
+</code></pre>
+<p>To <span title="hidden">what floor</span> do the instructions take <em>Santa</em>?</p>
+</article>
+<p>Your puzzle answer was <code>74</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now, given the same instructions.</p></article>
+</main></body></html>"#;
+        let expected = "## --- Day 1: Not Quite Lisp ---
+
+Santa is trying to deliver presents in a *large apartment building*, but he can't find the right floor.
+
+For example:
+
+- `(())` and `()()` both result in floor `0`.
+- `>` is `not` a [bracket](https://adventofcode.com/2015/about).
+
+```
+1 < 2
+  3 && 4
+```
+
+To what floor do the instructions take *Santa*?
+
+## --- Part Two ---
+
+Now, given the same instructions.
+";
+        let markdown = to_markdown(page, "https://adventofcode.com").unwrap();
+        assert_eq!(markdown, expected);
+        assert!(has_part_two(&markdown));
+        assert_eq!(to_markdown("<html>Not found</html>", ""), None);
+    }
+
+    #[test]
+    fn stray_bracket_test() {
+        let convert = |html| Converter::new("").convert(html);
+        assert_eq!(convert("<p>a < b, and b > a</p>"), "a < b, and b > a");
+        assert_eq!(convert("<p>1 <2</p>"), "1 <2");
+        assert_eq!(convert("<p>unterminated <em"), "unterminated <em");
+        assert_eq!(convert("<"), "<");
+        // closing tags which were never opened are left out
+        assert_eq!(convert("<p>a</code> b</p>"), "a b");
+        assert_eq!(convert("</pre><p><em>a</em></p>"), "*a*");
+        assert_eq!(
+            to_markdown("<article><p>a</code> b</p></article>", ""),
+            Some("a b\n".into())
+        );
+    }
+
+    #[test]
+    fn entity_test() {
+        assert_eq!(
+            decode_entities("a &amp; b &#39;c&#x27; &unknown; &"),
+            "a & b 'c' &unknown; &"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Edits to the `solutions!` list at the bottom of `src/lib.rs`, made by the `util` binary when it
+//! scaffolds or removes a day
+//!
+//! Edits only ever add or remove a single line, so the rest of the file, including any comments
+//! within the list, is left exactly as it was. Entries are kept in year and day order.
+use crate::Result;
+use std::ops::Range;
+
+/// The name of the module an entry registers, such as `y2015d07` for `    y2015d07: dot,`
+fn entry_name(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let end = line.find([':', ','])?;
+    let name = line[..end].trim();
+    (name.starts_with('y')
+        && name[1..]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_'))
+    .then_some(name)
+}
+
+/// The lines of the registry, with the byte range of each line, excluding its opening and closing
+fn registry_lines(lib: &str) -> Result<Vec<(Range<usize>, &str)>> {
+    let start = lib
+        .find("solutions! {\n")
+        .ok_or("No solution registry? You changed and broke something.")?
+        + "solutions! {\n".len();
+    let end = if lib[start..].starts_with('}') {
+        start
+    } else {
+        start
+            + lib[start..]
+                .find("\n}")
+                .map(|end| end + 1)
+                .ok_or("Solution registry is never closed? You changed and broke something.")?
+    };
+    let mut lines = Vec::new();
+    let mut offset = start;
+    for line in lib[start..end].split_inclusive('\n') {
+        lines.push((offset..offset + line.len(), line));
+        offset += line.len();
+    }
+    if lines.is_empty() {
+        // an empty registry still has somewhere to insert into
+        lines.push((start..start, ""));
+    }
+    Ok(lines)
+}
+
+/// Adds an entry for a module, after the entries which sort before it, unless it is already there
+pub fn insert(lib: &str, name: &str) -> Result<String> {
+    let lines = registry_lines(lib)?;
+    if lines.iter().any(|(_, line)| entry_name(line) == Some(name)) {
+        return Ok(lib.to_string());
+    }
+    let entries = lines
+        .iter()
+        .filter(|(_, line)| entry_name(line).is_some())
+        .collect::<Vec<_>>();
+    let indent = entries
+        .first()
+        .map(|(_, line)| &line[..line.len() - line.trim_start().len()])
+        .unwrap_or("    ");
+    // inserted after the last entry which sorts before it, so a comment stays above its entry
+    let at = match entries
+        .iter()
+        .rev()
+        .find(|(_, line)| entry_name(line).is_some_and(|entry| entry < name))
+    {
+        Some((range, _)) => range.end,
+        None => lines[0].0.start,
+    };
+    let mut lib = lib.to_string();
+    lib.insert_str(at, &format!("{}{},\n", indent, name));
+    Ok(lib)
+}
+
+/// Removes the entry for a module
+pub fn remove(lib: &str, name: &str) -> Result<String> {
+    let (range, _) = registry_lines(lib)?
+        .into_iter()
+        .find(|(_, line)| entry_name(line) == Some(name))
+        .ok_or_else(|| format!("{} is not registered", name))?;
+    let mut lib = lib.to_string();
+    lib.replace_range(range, "");
+    Ok(lib)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod vm;
+
+solutions! {
+    y2015d01,
+    // wires, with a graph export
+    y2015d07: dot,
+    y2021d12: dot,
+}
+";
+
+    #[test]
+    fn insert_test() {
+        assert_eq!(
+            insert(LIB, "y2015d09").unwrap(),
+            LIB.replace("y2015d07: dot,\n", "y2015d07: dot,\n    y2015d09,\n")
+        );
+        assert_eq!(
+            insert(LIB, "y2015d05").unwrap(),
+            LIB.replace("y2015d01,\n", "y2015d01,\n    y2015d05,\n")
+        );
+        assert_eq!(
+            insert(LIB, "y2014d01").unwrap(),
+            LIB.replace("{\n", "{\n    y2014d01,\n")
+        );
+        assert_eq!(
+            insert(LIB, "y2022d01").unwrap(),
+            LIB.replace("dot,\n}", "dot,\n    y2022d01,\n}")
+        );
+        assert_eq!(
+            insert(LIB, "y2015d07_fast").unwrap(),
+            LIB.replace("y2015d07: dot,\n", "y2015d07: dot,\n    y2015d07_fast,\n")
+        );
+        assert_eq!(insert(LIB, "y2015d07").unwrap(), LIB);
+        assert_eq!(
+            insert("solutions! {\n}\n", "y2015d01").unwrap(),
+            "solutions! {\n    y2015d01,\n}\n"
+        );
+        assert!(insert("pub mod vm;\n", "y2015d01").is_err());
+    }
+
+    #[test]
+    fn remove_test() {
+        assert_eq!(
+            remove(LIB, "y2015d01").unwrap(),
+            LIB.replace("    y2015d01,\n", "")
+        );
+        assert_eq!(
+            remove(LIB, "y2021d12").unwrap(),
+            LIB.replace("    y2021d12: dot,\n", "")
+        );
+        assert_eq!(
+            remove(LIB, "y2015d02").unwrap_err().to_string(),
+            "y2015d02 is not registered"
+        );
+    }
+}
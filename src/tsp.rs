@@ -0,0 +1,282 @@
+//! Travelling salesman solvers, for the advent of code problems which visit every location once
+//!
+//! Routes are found with the Held-Karp algorithm, which takes `O(2^n n^2)` time and `O(2^n n)`
+//! memory rather than the `O(n!)` of trying every permutation, so is usable up to around twenty
+//! nodes, and returns an error for any more than [`MAX_NODES`].
+use crate::{
+    graph::{Graph, NodeId},
+    Result,
+};
+use std::{hash::Hash, ops::Add};
+
+/// The most nodes a route may visit, as the table of routes grows by `2^n n`
+pub const MAX_NODES: usize = 20;
+
+/// Whether to find the cheapest or the most expensive route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    Shortest,
+    Longest,
+}
+
+impl Goal {
+    fn prefers<W: Ord>(self, candidate: W, current: W) -> bool {
+        match self {
+            Goal::Shortest => candidate < current,
+            Goal::Longest => candidate > current,
+        }
+    }
+}
+
+/// Whether a route ends wherever it finishes, or returns to where it started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tour {
+    Path,
+    Cycle,
+}
+
+/// Finds the best route visiting each of the nodes `0..len` exactly once, alongside its weight
+///
+/// `weight` gives the weight of travelling from one node to another, or `None` if that is not
+/// possible. Cycles always start from node `0`, and a cycle of a single node has no weight.
+/// Returns `None` if there are no nodes or no route visits them all, and an error if there are more
+/// than [`MAX_NODES`] nodes.
+pub fn held_karp<W>(
+    len: usize,
+    weight: impl FnMut(NodeId, NodeId) -> Option<W>,
+    goal: Goal,
+    tour: Tour,
+) -> Result<Option<(Vec<NodeId>, W)>>
+where
+    W: Copy + Ord + Default + Add<Output = W>,
+{
+    if len > MAX_NODES {
+        return Err(format!("Held-Karp is limited to {} nodes, not {}", MAX_NODES, len).into());
+    }
+    Ok(best_route(len, weight, goal, tour))
+}
+
+/// [`held_karp`], for any number of nodes
+fn best_route<W>(
+    len: usize,
+    mut weight: impl FnMut(NodeId, NodeId) -> Option<W>,
+    goal: Goal,
+    tour: Tour,
+) -> Option<(Vec<NodeId>, W)>
+where
+    W: Copy + Ord + Default + Add<Output = W>,
+{
+    if len == 0 {
+        return None;
+    }
+    let weights: Vec<Option<W>> = (0..len)
+        .flat_map(|from| (0..len).map(move |to| (from, to)))
+        .map(|(from, to)| weight(from, to))
+        .collect();
+    let weight = |from: NodeId, to: NodeId| weights[from * len + to];
+
+    // the best weight of a route visiting the nodes in `visited` and ending at `last` is stored at
+    // `visited * len + last`, alongside the node visited before `last`
+    let full = (1 << len) - 1;
+    let mut best: Vec<Option<(W, NodeId)>> = vec![None; (full + 1) * len];
+    let starts = match tour {
+        Tour::Path => 0..len,
+        Tour::Cycle => 0..1,
+    };
+    for start in starts {
+        best[(1 << start) * len + start] = Some((W::default(), start));
+    }
+    for visited in 1..=full {
+        for last in (0..len).filter(|last| visited & (1 << last) != 0) {
+            let Some((cost, _)) = best[visited * len + last] else {
+                continue;
+            };
+            for next in (0..len).filter(|next| visited & (1 << next) == 0) {
+                let Some(step) = weight(last, next) else {
+                    continue;
+                };
+                let candidate = cost + step;
+                let entry = &mut best[(visited | (1 << next)) * len + next];
+                if entry.is_none_or(|(current, _)| goal.prefers(candidate, current)) {
+                    *entry = Some((candidate, last));
+                }
+            }
+        }
+    }
+
+    let (total, last) = (0..len)
+        .filter_map(|last| {
+            let (cost, _) = best[full * len + last]?;
+            match tour {
+                Tour::Cycle if len > 1 => Some((cost + weight(last, 0)?, last)),
+                _ => Some((cost, last)),
+            }
+        })
+        .reduce(|a, b| if goal.prefers(b.0, a.0) { b } else { a })?;
+
+    let mut route = vec![last];
+    let (mut visited, mut node) = (full, last);
+    while visited != 1 << node {
+        let (_, previous) = best[visited * len + node]?;
+        visited &= !(1 << node);
+        node = previous;
+        route.push(node);
+    }
+    route.reverse();
+    Some((route, total))
+}
+
+/// Finds the cheapest route through a graph visiting every node once
+pub fn shortest_path<N, W>(graph: &Graph<N, W>) -> Result<Option<(Vec<NodeId>, W)>>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Default + Add<Output = W>,
+{
+    held_karp(
+        graph.len(),
+        |a, b| graph.weight(a, b),
+        Goal::Shortest,
+        Tour::Path,
+    )
+}
+
+/// Finds the most expensive route through a graph visiting every node once
+pub fn longest_path<N, W>(graph: &Graph<N, W>) -> Result<Option<(Vec<NodeId>, W)>>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Default + Add<Output = W>,
+{
+    held_karp(
+        graph.len(),
+        |a, b| graph.weight(a, b),
+        Goal::Longest,
+        Tour::Path,
+    )
+}
+
+/// Finds the cheapest round trip through a graph visiting every node once
+pub fn shortest_cycle<N, W>(graph: &Graph<N, W>) -> Result<Option<(Vec<NodeId>, W)>>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Default + Add<Output = W>,
+{
+    held_karp(
+        graph.len(),
+        |a, b| graph.weight(a, b),
+        Goal::Shortest,
+        Tour::Cycle,
+    )
+}
+
+/// Finds the most expensive round trip through a graph visiting every node once
+pub fn longest_cycle<N, W>(graph: &Graph<N, W>) -> Result<Option<(Vec<NodeId>, W)>>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Default + Add<Output = W>,
+{
+    held_karp(
+        graph.len(),
+        |a, b| graph.weight(a, b),
+        Goal::Longest,
+        Tour::Cycle,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    /// The weight of a route, checking it visits every node once and only uses existing edges
+    fn route_weight(len: usize, weights: &[Vec<i32>], route: &[NodeId], tour: Tour) -> i32 {
+        assert_eq!(
+            route.iter().sorted().copied().collect::<Vec<_>>(),
+            (0..len).collect::<Vec<_>>()
+        );
+        let closing = match tour {
+            Tour::Cycle => Some((route[len - 1], route[0])),
+            Tour::Path => None,
+        };
+        route
+            .iter()
+            .copied()
+            .tuple_windows()
+            .chain(closing)
+            .map(|(a, b)| weights[a][b])
+            .sum()
+    }
+
+    #[test]
+    fn brute_force_test() {
+        // an asymmetric graph with negative weights, compared against trying every permutation
+        let len = 7;
+        let weights: Vec<Vec<i32>> = (0..len)
+            .map(|a| {
+                (0..len)
+                    .map(|b| ((a * 37 + b * 11) % 23) as i32 - 8)
+                    .collect()
+            })
+            .collect();
+        for goal in [Goal::Shortest, Goal::Longest] {
+            for tour in [Tour::Path, Tour::Cycle] {
+                let brute = (0..len)
+                    .permutations(len)
+                    .map(|route| route_weight(len, &weights, &route, tour));
+                let expected = match goal {
+                    Goal::Shortest => brute.min(),
+                    Goal::Longest => brute.max(),
+                };
+                let (route, total) = held_karp(len, |a, b| Some(weights[a][b]), goal, tour)
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(Some(total), expected, "{:?} {:?}", goal, tour);
+                assert_eq!(route_weight(len, &weights, &route, tour), total);
+            }
+        }
+    }
+
+    #[test]
+    fn graph_test() {
+        let mut graph = Graph::new();
+        graph.add_undirected_edge("London", "Dublin", 464);
+        graph.add_undirected_edge("London", "Belfast", 518);
+        graph.add_undirected_edge("Dublin", "Belfast", 141);
+        let names = |(route, total): (Vec<NodeId>, u32)| {
+            (
+                route
+                    .into_iter()
+                    .map(|id| *graph.node(id))
+                    .collect::<Vec<_>>(),
+                total,
+            )
+        };
+        assert_eq!(
+            shortest_path(&graph).unwrap().map(names),
+            Some((vec!["Belfast", "Dublin", "London"], 605))
+        );
+        assert_eq!(longest_path(&graph).unwrap().unwrap().1, 982);
+        assert_eq!(shortest_cycle(&graph).unwrap().unwrap().1, 1123);
+        assert_eq!(longest_cycle(&graph).unwrap().unwrap().1, 1123);
+
+        // a route must use existing edges, and there is none back to the start
+        let mut line = Graph::new();
+        line.add_edge(0, 1, 1);
+        line.add_edge(1, 2, 1);
+        assert_eq!(shortest_path(&line).unwrap(), Some((vec![0, 1, 2], 2)));
+        assert_eq!(shortest_cycle(&line).unwrap(), None);
+        assert_eq!(
+            held_karp(0, |_, _| Some(0), Goal::Shortest, Tour::Path).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn limit_test() {
+        assert_eq!(
+            held_karp(MAX_NODES + 1, |_, _| Some(1), Goal::Shortest, Tour::Path)
+                .unwrap_err()
+                .to_string(),
+            "Held-Karp is limited to 20 nodes, not 21"
+        );
+    }
+}
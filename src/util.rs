@@ -1,21 +1,25 @@
-use aoc::*;
-use hyper::{body, client::Builder, header::COOKIE, Body, Method, Request};
-use hyper_tls::HttpsConnector;
+use aoc::{
+    calendar::{self, Clock, SystemClock},
+    client::{Client, ClientError, Config, Verdict},
+    guesses::Guesses,
+    leaderboard::{self, Leaderboard},
+    *,
+};
 use std::{
-    fs::{self, File, OpenOptions},
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, File},
     io::Write,
-    path::PathBuf,
-    process::Command,
+    path::{Path, PathBuf},
     str::FromStr,
+    time::{Duration, Instant},
 };
-use toml::{map::Map, value::Value};
 
 const SCAFFOLD: &str = r#"
 use aoc::*;
 
 struct ParsedInput {}
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     todo!()
 }
 
@@ -33,54 +37,120 @@ fn test() {}
 aoc_main!(parse, task1, task2);
 "#;
 
-#[derive(serde::Deserialize, Debug)]
-struct Config {
-    config: ConfigInner,
-}
-#[derive(serde::Deserialize, Debug)]
-struct ConfigInner {
-    session: String,
-}
-
 const CARGO_ROOT: &str = env!("CARGO_MANIFEST_DIR");
 
-use chrono::{Datelike, FixedOffset, TimeZone, Utc};
 use structopt::StructOpt;
 #[derive(StructOpt)]
 #[structopt(name = "Advent Of Code Utility")]
 enum Opt {
+    /// Download the input and puzzle for a day, and create and register a solution for it
     Scaffold {
         #[structopt(short, long)]
         year: Option<i32>,
         #[structopt(short, long)]
         day: Option<u32>,
+        /// Where to save the input, by default `inputs/y####d##.txt`
+        #[structopt(long, parse(from_os_str))]
+        path: Option<PathBuf>,
+        /// The config to use, by default `Advent.toml` in the crate root
+        #[structopt(long, parse(from_os_str))]
+        config: Option<PathBuf>,
+        /// Only download the input
+        #[structopt(long, conflicts_with = "bin")]
+        download_only: bool,
+        /// The name of the solution, by default `y####d##`, which other names must start with
+        #[structopt(long)]
+        bin: Option<String>,
     },
+    /// Run the solution for a day against its input, or every solution with `--all`
     Run {
         #[structopt(short, long)]
         year: Option<i32>,
         #[structopt(short, long)]
         day: Option<u32>,
+        /// The input to run against, by default `inputs/y####d##.txt`
+        #[structopt(long, parse(from_os_str))]
+        path: Option<PathBuf>,
+        /// The name of the solution to run, by default `y####d##`
+        #[structopt(long)]
+        bin: Option<String>,
+        /// Run every registered solution (in the given year, if one is provided)
+        #[structopt(long, conflicts_with_all = &["day", "bin", "path"])]
+        all: bool,
+    },
+    /// Check solutions against the answers recorded in `answers/`
+    Verify {
+        #[structopt(short, long)]
+        year: Option<i32>,
+        #[structopt(short, long)]
+        day: Option<u32>,
+    },
+    /// Submit an answer, recording the verdict so the same answer is never submitted twice
+    Submit {
+        #[structopt(short, long)]
+        year: Option<i32>,
+        #[structopt(short, long)]
+        day: Option<u32>,
+        /// The part to submit, either `1` or `2`
+        #[structopt(short, long)]
+        part: Part,
+        /// The answer to submit, by default the answer given by running the solution
+        answer: Option<String>,
+    },
+    /// Inspect the answers submitted so far, recorded in `inputs/`
+    Guesses(GuessesOpt),
+    /// Save the puzzle description as Markdown in `puzzles/`, if part two is not already saved
+    Puzzle {
+        #[structopt(short, long)]
+        year: Option<i32>,
+        #[structopt(short, long)]
+        day: Option<u32>,
+        /// Fetch the description even if it is already complete
+        #[structopt(long)]
+        refresh: bool,
+    },
+    /// Delete the solution for a day, and unregister it, leaving its input and answers
+    Remove {
+        #[structopt(short, long)]
+        year: i32,
+        #[structopt(short, long)]
+        day: u32,
+        /// The name of the solution to remove, by default `y####d##`
+        #[structopt(long)]
+        bin: Option<String>,
+    },
+    /// Show which days have stars, solutions and inputs, for each year
+    Stats {
+        #[structopt(short, long)]
+        year: Option<i32>,
+        /// Use the answers recorded locally for stars, rather than fetching each year's calendar
+        #[structopt(long)]
+        offline: bool,
+    },
+    /// Count down to the next puzzle, then download and scaffold it as soon as it unlocks
+    Wait {
+        /// How many seconds to wait after the puzzle unlocks, in case the clocks differ
+        #[structopt(long, default_value = "2")]
+        delay: u64,
+    },
+    /// Show a private leaderboard, which is fetched at most once every 15 minutes
+    Leaderboard {
+        /// The id of the leaderboard, which is the number in its URL
+        id: u64,
+        #[structopt(short, long)]
+        year: Option<i32>,
     },
 }
 
-async fn download(year: i32, day: u32, session: &str, writer: &mut impl Write) -> Result<()> {
-    let body = Builder::default()
-        .build(HttpsConnector::new())
-        .request(
-            Request::builder()
-                .method(Method::GET)
-                .header(COOKIE, format!("session={}", session))
-                .uri(format!(
-                    "https://adventofcode.com/{}/day/{}/input",
-                    year, day
-                ))
-                .body(Body::empty())?,
-        )
-        .await?
-        .into_body();
-    let bytes = body::to_bytes(body).await?;
-    writer.write_all(&bytes)?;
-    Ok(())
+#[derive(StructOpt)]
+enum GuessesOpt {
+    /// List every answer submitted for a day, and the range any further answer must be within
+    List {
+        #[structopt(short, long)]
+        year: Option<i32>,
+        #[structopt(short, long)]
+        day: Option<u32>,
+    },
 }
 
 fn scaffold_rust(writer: &mut impl Write) -> Result<()> {
@@ -99,54 +169,61 @@ fn input_path(year: i32, day: u32) -> Result<PathBuf> {
     Ok(path)
 }
 
-fn bin_path(year: i32, day: u32) -> Result<PathBuf> {
+fn bin_path(name: &str) -> Result<PathBuf> {
     let mut path = PathBuf::from_str(CARGO_ROOT)?;
     path.push("src");
-    path.push(format!("{}.rs", base_yd_path(year, day)));
+    path.push(format!("{}.rs", name));
     Ok(path)
 }
 
-fn add_cargo_bin(year: i32, day: u32) -> Result<()> {
+fn lib_path() -> Result<PathBuf> {
     let mut path = PathBuf::from_str(CARGO_ROOT)?;
-    path.push("Cargo.toml");
-    let cargo_string = fs::read_to_string(&path)?;
-    let mut cargo_config: toml::Value = toml::from_str(&cargo_string)?;
-    let bin_name = base_yd_path(year, day);
-    let bins = cargo_config
-        .get_mut("bin")
-        .and_then(|v| v.as_array_mut())
-        .ok_or("No bins configured? You changed and broke something.")?;
-    if !bins
-        .iter()
-        .any(|v| v.get("name").and_then(|v| v.as_str()) == Some(&bin_name))
-    {
-        bins.push(Value::Table(Map::from_iter([
-            ("name".into(), Value::String(bin_name)),
-            (
-                "path".into(),
-                Value::String(format!("src/{}.rs", base_yd_path(year, day))),
-            ),
-        ])))
+    path.push("src");
+    path.push("lib.rs");
+    Ok(path)
+}
+
+fn register_solution(name: &str) -> Result<()> {
+    let path = lib_path()?;
+    let lib = fs::read_to_string(&path)?;
+    let registered = registry::insert(&lib, name)?;
+    // left alone if it is already registered, so the crate is not rebuilt for nothing
+    if registered != lib {
+        fs::write(path, registered)?;
     }
-    let mut cargo_file = OpenOptions::new()
-        .read(true)
-        .write(true)
-        .create(false)
-        .truncate(true)
-        .open(path)?;
-    cargo_file.write_all(toml::to_string_pretty(&cargo_config)?.as_bytes())?;
     Ok(())
 }
 
-fn session() -> Result<String> {
-    let Config {
-        config: ConfigInner { session },
-    } = toml::from_str::<Config>(&fs::read_to_string({
-        let mut path = PathBuf::from_str(CARGO_ROOT)?;
-        path.push("Advent.toml");
-        path
-    })?)?;
-    Ok(session)
+/// Deletes a solution, and its entry in the registry
+fn remove(name: &str) -> Result<()> {
+    let path = lib_path()?;
+    let lib = fs::read_to_string(&path)?;
+    // unregistered first, as a module whose file is missing would stop the crate building at all
+    fs::write(&path, registry::remove(&lib, name)?)?;
+    let bin_path = bin_path(name)?;
+    if bin_path.exists() {
+        if let Err(e) = fs::remove_file(&bin_path) {
+            fs::write(&path, lib)?;
+            return Err(e.into());
+        }
+        println!("Removed {}", bin_path.display());
+    }
+    Ok(())
+}
+
+/// A client for the website, configured by `Advent.toml` and throttled across runs
+fn client() -> Result<Client> {
+    let mut config_path = PathBuf::from_str(CARGO_ROOT)?;
+    config_path.push("Advent.toml");
+    client_with_config(config_path)
+}
+
+/// A client configured by the given file rather than `Advent.toml`
+fn client_with_config(config_path: PathBuf) -> Result<Client> {
+    let mut throttle_path = PathBuf::from_str(CARGO_ROOT)?;
+    throttle_path.push("target");
+    throttle_path.push("advent-last-request");
+    Ok(Client::new(&Config::load(config_path)?).with_throttle_file(throttle_path))
 }
 
 fn cleanup_year(year: i32) -> i32 {
@@ -157,231 +234,512 @@ fn cleanup_year(year: i32) -> i32 {
     }
 }
 
-fn recent_aoc_date() -> (i32, u32) {
-    let mut date = TimeZone::timestamp_nanos(
-        &FixedOffset::west_opt(18_000).unwrap(),
-        Utc::now().timestamp_nanos(),
-    )
-    .date_naive();
-
-    // it's not december yet
-    if date.month() < 12 {
-        date = date.with_year(date.year() - 1).unwrap();
-    }
-
-    // it's not advent of code days
-    if date.day() > 25 {
-        date = date.with_day(25).unwrap();
+/// Saves the description of a puzzle, unless both parts are already saved
+async fn save_puzzle(client: &Client, year: i32, day: u32, refresh: bool) -> Result<()> {
+    let path = puzzle::path(year, day);
+    let saved = fs::read_to_string(&path).unwrap_or_default();
+    if puzzle::has_part_two(&saved) && !refresh {
+        return Ok(());
     }
-
-    (date.year(), date.month())
+    let markdown = client.puzzle(year, day).await?;
+    fs::create_dir_all(path.parent().ok_or("Puzzles have no directory")?)?;
+    fs::write(&path, markdown)?;
+    println!("Saved the puzzle description to {}", path.display());
+    Ok(())
 }
 
-async fn scaffold(year: i32, day: u32) -> Result<()> {
-    let input_path = input_path(year, day)?;
+/// Downloads the input for a day to the given path, then unless `bin` is `None`, also creates and
+/// registers a solution with that name and saves the puzzle, which only reports an error if it fails
+async fn scaffold(
+    client: &Client,
+    year: i32,
+    day: u32,
+    input_path: &Path,
+    bin: Option<&str>,
+) -> Result<()> {
+    if let Some(bin) = bin {
+        // the year and day of a solution are read from its name when it is registered
+        if !bin.starts_with(&base_yd_path(year, day)) {
+            return Err(format!("{} does not start with {}", bin, base_yd_path(year, day)).into());
+        }
+        if !bin.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("{} is not a valid module name", bin).into());
+        }
+    }
     if !input_path.exists() {
-        let mut input_file = File::create(input_path)?;
-        let session = session()?;
-        download(year, day, &session, &mut input_file).await?;
+        // only written once the download succeeds, so a failure is retried on the next run
+        let input = client.input(year, day).await?;
+        if let Some(parent) = input_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(input_path, input)?;
     }
-    let bin_path = bin_path(year, day)?;
+    let Some(bin) = bin else {
+        return Ok(());
+    };
+    let bin_path = bin_path(bin)?;
     if !bin_path.exists() {
         let mut bin_file = File::create(bin_path)?;
         scaffold_rust(&mut bin_file)?;
-        add_cargo_bin(year, day)?;
+    }
+    // registered even if the file was already there, in case registering it failed before
+    register_solution(bin)?;
+    // the puzzle is only there to read, and can be fetched again with `puzzle`
+    if let Err(e) = save_puzzle(client, year, day, false).await {
+        eprintln!("Could not save the puzzle description: {}", e);
+    }
+    Ok(())
+}
+
+fn run(name: &str, input_path: &Path) -> Result<()> {
+    let solution = solution_named(name).ok_or_else(|| format!("No solution named {}", name))?;
+    let input = fs::read_to_string(input_path)?;
+    println!("{}", solution.run(&input, None)?);
+    Ok(())
+}
+
+enum RunStatus {
+    Solved(Answers),
+    MissingInput,
+    ParseFailed(String),
+    Failed(String),
+}
+
+struct RunReport {
+    year: i32,
+    day: u32,
+    status: RunStatus,
+    elapsed: Duration,
+}
+
+fn run_report(solution: &Solution) -> Result<RunReport> {
+    let input_path = input_path(solution.year, solution.day)?;
+    let mut report = RunReport {
+        year: solution.year,
+        day: solution.day,
+        status: RunStatus::MissingInput,
+        elapsed: Duration::ZERO,
+    };
+    if !input_path.exists() {
+        return Ok(report);
+    }
+    let input = fs::read_to_string(input_path)?;
+    let start = Instant::now();
+    let result = solution.run(&input, None);
+    report.elapsed = start.elapsed();
+    report.status = match result {
+        Ok(answers) => RunStatus::Solved(answers),
+        Err(e) if e.is::<ParseError>() => RunStatus::ParseFailed(e.to_string()),
+        Err(e) => RunStatus::Failed(e.to_string()),
+    };
+    Ok(report)
+}
+
+/// Shortens a cell of the summary table to a single line of at most `width` characters
+fn table_cell(text: &str, width: usize) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    if line.chars().count() > width || text.lines().nth(1).is_some() {
+        let mut cell: String = line.chars().take(width - 1).collect();
+        cell.push('…');
+        cell
+    } else {
+        line.to_string()
+    }
+}
+
+fn run_all(year: Option<i32>) -> Result<()> {
+    let mut reports = Vec::new();
+    for solution in SOLUTIONS
+        .iter()
+        .filter(|solution| year.is_none_or(|year| solution.year == year))
+    {
+        eprintln!("Running {}", base_yd_path(solution.year, solution.day));
+        reports.push(run_report(solution)?);
+    }
+
+    println!(
+        "{:<10} {:<20} {:<20} {:>10} {:>10} {:>10} {:>10}  Status",
+        "Day", "Task 1", "Task 2", "Parse", "Task 1", "Task 2", "Total"
+    );
+    let mut total = Duration::ZERO;
+    for report in &reports {
+        total += report.elapsed;
+        let time = |duration: Duration| format!("{:.2?}", duration);
+        let (task1, task2, [parse_time, task1_time, task2_time], status) = match &report.status {
+            RunStatus::Solved(answers) => (
+                answers.task1.as_deref().unwrap_or_default(),
+                answers.task2.as_deref().unwrap_or_default(),
+                [
+                    time(answers.timings.parse),
+                    time(answers.timings.task1),
+                    time(answers.timings.task2),
+                ],
+                "ok",
+            ),
+            RunStatus::MissingInput => ("", "", Default::default(), "missing input"),
+            RunStatus::ParseFailed(_) => ("", "", Default::default(), "parse failed"),
+            RunStatus::Failed(_) => ("", "", Default::default(), "failed"),
+        };
+        println!(
+            "{:<10} {:<20} {:<20} {:>10} {:>10} {:>10} {:>10}  {}",
+            base_yd_path(report.year, report.day),
+            table_cell(task1, 20),
+            table_cell(task2, 20),
+            parse_time,
+            task1_time,
+            task2_time,
+            time(report.elapsed),
+            status,
+        );
+    }
+    println!(
+        "{:<10} {:<20} {:<20} {:>10} {:>10} {:>10} {:>10}",
+        "Total",
+        "",
+        "",
+        "",
+        "",
+        "",
+        format!("{:.2?}", total)
+    );
+
+    for report in &reports {
+        if let RunStatus::ParseFailed(e) | RunStatus::Failed(e) = &report.status {
+            println!();
+            println!("{}:", base_yd_path(report.year, report.day));
+            println!("{}", table_cell(e, 100));
+        }
     }
     Ok(())
 }
 
-fn run(year: i32, day: u32) -> Result<()> {
-    Command::new("cargo")
-        .arg("run")
-        .arg("--release")
-        .arg("--bin")
-        .arg(base_yd_path(year, day))
-        .arg("--")
-        .arg(input_path(year, day)?)
-        .spawn()?
-        .wait()?;
+fn verify(year: Option<i32>, day: Option<u32>) -> Result<()> {
+    let mut failures = 0;
+    for solution in SOLUTIONS.iter().filter(|solution| {
+        year.is_none_or(|year| solution.year == year) && day.is_none_or(|day| solution.day == day)
+    }) {
+        let name = base_yd_path(solution.year, solution.day);
+        let Some(accepted) = AcceptedAnswers::load(solution.year, solution.day)? else {
+            println!("{} no accepted answers", name);
+            continue;
+        };
+        let input_path = input_path(solution.year, solution.day)?;
+        if !input_path.exists() {
+            println!("{} missing input", name);
+            continue;
+        }
+        match solution
+            .run(&fs::read_to_string(input_path)?, None)
+            .and_then(|answers| accepted.verify(&answers))
+        {
+            Ok(()) => println!("{} ok", name),
+            Err(e) => {
+                failures += 1;
+                println!("{} FAILED\n{}", name, e);
+            }
+        }
+    }
+    if failures > 0 {
+        Err(format!(
+            "{} solutions did not match their accepted answers",
+            failures
+        )
+        .into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs the solution for one part, giving its answer as it should be submitted
+fn solve(year: i32, day: u32, part: Part) -> Result<String> {
+    let solution = solution(year, day).ok_or("No solution registered for that day")?;
+    let input = fs::read_to_string(input_path(year, day)?)?;
+    let answers = solution.run(&input, Some(part))?;
+    let answer = match part {
+        Part::One => answers.task1,
+        Part::Two => answers.task2,
+    };
+    let answer = answer.ok_or("The solution did not give an answer")?;
+    // answers are debug formatted, so string answers are quoted and escaped
+    Ok(serde_json::from_str::<String>(&answer).unwrap_or(answer))
+}
+
+async fn submit(year: i32, day: u32, part: Part, answer: Option<String>) -> Result<()> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => solve(year, day, part)?,
+    };
+    let guesses_path = Guesses::path(year, day);
+    let mut guesses = Guesses::load(&guesses_path)?;
+    guesses.check(part, &answer)?;
+    println!("Submitting {} for part {}", answer, part);
+    let client = client()?;
+    let verdict = client.submit(year, day, part, &answer).await?;
+    guesses.record(part, &answer, verdict);
+    guesses.save(&guesses_path)?;
+    println!("{}: {}", answer, verdict);
+    if verdict == Verdict::Correct && part == Part::One {
+        // part two is only described once part one is solved, and the answer was recorded either way
+        if let Err(e) = save_puzzle(&client, year, day, true).await {
+            eprintln!("Could not save the puzzle description: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn list_guesses(year: i32, day: u32) -> Result<()> {
+    let guesses = Guesses::load(Guesses::path(year, day))?;
+    println!("{}", base_yd_path(year, day));
+    for part in [Part::One, Part::Two] {
+        println!("Part {}:", part);
+        for guess in guesses.part(part) {
+            let verdict = guess.verdict.to_string();
+            println!("  {:<20} {:<10} {}", guess.answer, verdict, guess.submitted);
+        }
+        match guesses.bounds(part) {
+            (None, None) => {}
+            (too_low, too_high) => println!(
+                "  must be above {} and below {}",
+                too_low.map_or("-".to_string(), |n| n.to_string()),
+                too_high.map_or("-".to_string(), |n| n.to_string()),
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Waits for the next puzzle to unlock, then scaffolds it, retrying if the website disagrees that
+/// it has unlocked
+async fn wait(clock: &impl Clock, delay: Duration) -> Result<()> {
+    // set up before waiting, so a missing config is found straight away
+    let client = client()?;
+    let (year, day) = calendar::next_day(clock.now());
+    let input_path = input_path(year, day)?;
+    let bin = base_yd_path(year, day);
+    let unlock = calendar::unlock_time(year, day).ok_or("The next puzzle has no unlock time")?;
+    let start = unlock + chrono::Duration::from_std(delay)?;
+    println!("Waiting for {}", bin);
+    calendar::wait_until(clock, start, |remaining| {
+        let seconds = remaining.num_seconds();
+        eprint!(
+            "\r{}d {:02}:{:02}:{:02} ",
+            seconds / 86_400,
+            seconds / 3600 % 24,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+    })
+    .await;
+    eprintln!();
+    let mut attempts = 1;
+    loop {
+        match scaffold(&client, year, day, &input_path, Some(&bin)).await {
+            Err(e) if attempts < 5 && e.downcast_ref() == Some(&ClientError::NotUnlocked) => {
+                eprintln!("{} has not unlocked yet, trying again", bin);
+                attempts += 1;
+                clock.sleep(Duration::from_secs(5)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// The years with a downloaded input, from the names of the files in `inputs/`
+fn input_years() -> Result<BTreeSet<i32>> {
+    let mut path = PathBuf::from_str(CARGO_ROOT)?;
+    path.push("inputs");
+    let Ok(entries) = fs::read_dir(path) else {
+        return Ok(BTreeSet::new());
+    };
+    let mut years = BTreeSet::new();
+    for entry in entries {
+        let name = entry?.file_name();
+        let name = name.to_string_lossy();
+        if let Some(year) = name
+            .strip_prefix('y')
+            .and_then(|name| name.get(..4))
+            .and_then(|year| year.parse().ok())
+        {
+            years.insert(year);
+        }
+    }
+    Ok(years)
+}
+
+/// The stars for each day of a year, going by the accepted answers in `answers/` and the correct
+/// answers in the guesses ledgers
+fn recorded_stars(year: i32) -> Result<BTreeMap<u32, u8>> {
+    let mut stars = BTreeMap::new();
+    for day in 1..=25 {
+        let accepted = AcceptedAnswers::load(year, day)?;
+        let guesses = Guesses::load(Guesses::path(year, day))?;
+        let count = [Part::One, Part::Two]
+            .into_iter()
+            .filter(|&part| {
+                let accepted = accepted.as_ref().and_then(|accepted| match part {
+                    Part::One => accepted.task1.as_ref(),
+                    Part::Two => accepted.task2.as_ref(),
+                });
+                accepted.is_some()
+                    || guesses
+                        .part(part)
+                        .any(|guess| guess.verdict == Verdict::Correct)
+            })
+            .count();
+        stars.insert(day, count as u8);
+    }
+    Ok(stars)
+}
+
+/// Prints a grid of the days of each year, showing the stars collected, the solutions written and
+/// the inputs downloaded
+async fn stats(year: Option<i32>, offline: bool) -> Result<()> {
+    let years = match year {
+        Some(year) => BTreeSet::from([year]),
+        None => {
+            let mut years = input_years()?;
+            years.extend(SOLUTIONS.iter().map(|solution| solution.year));
+            years
+        }
+    };
+    let client = if offline {
+        None
+    } else {
+        match client() {
+            Ok(client) => Some(client),
+            Err(e) => {
+                eprintln!("Using the answers recorded locally for stars: {}", e);
+                None
+            }
+        }
+    };
+    for year in years {
+        let mut fetched = None;
+        if let Some(client) = &client {
+            match client.stars(year).await {
+                Ok(stars) => fetched = Some(stars),
+                Err(e) => eprintln!("Could not fetch the calendar for {}: {}", year, e),
+            }
+        }
+        let (stars, source) = match fetched {
+            Some(stars) => (stars, "from the calendar"),
+            None => (recorded_stars(year)?, "recorded locally"),
+        };
+        let solved = |day| solution(year, day).is_some();
+        let downloaded = |day| input_path(year, day).map(|path| path.exists());
+        let mut solution_row = String::new();
+        let mut input_row = String::new();
+        let mut missing_inputs = Vec::new();
+        for day in 1..=25 {
+            solution_row.push(if solved(day) { '#' } else { '.' });
+            let downloaded = downloaded(day)?;
+            input_row.push(if downloaded { '#' } else { '.' });
+            if solved(day) && !downloaded {
+                missing_inputs.push(day.to_string());
+            }
+        }
+        let star_row: String = (1..=25)
+            .map(|day| match stars.get(&day) {
+                Some(2) => '*',
+                Some(1) => '+',
+                _ => '.',
+            })
+            .collect();
+        let count = |row: &str| row.chars().filter(|&c| c == '#').count();
+
+        println!("{:<9} 1234567890123456789012345", year);
+        println!(
+            "{:<9} {}  {}/50 {}",
+            "Stars",
+            star_row,
+            stars.values().map(|&count| u32::from(count)).sum::<u32>(),
+            source
+        );
+        println!(
+            "{:<9} {}  {}/25",
+            "Solution",
+            solution_row,
+            count(&solution_row)
+        );
+        println!("{:<9} {}  {}/25", "Input", input_row, count(&input_row));
+        if !missing_inputs.is_empty() {
+            println!("Solutions without an input: {}", missing_inputs.join(", "));
+        }
+        println!();
+    }
     Ok(())
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let (recent_year, recent_day) = recent_aoc_date();
+    let (recent_year, recent_day) = calendar::recent_day(SystemClock.now());
     match Opt::from_args() {
-        Opt::Scaffold { year, day } => {
+        Opt::Scaffold {
+            year,
+            day,
+            path,
+            config,
+            download_only,
+            bin,
+        } => {
+            let year = year.map(cleanup_year).unwrap_or(recent_year);
+            let day = day.unwrap_or(recent_day);
+            let client = match config {
+                Some(config) => client_with_config(config)?,
+                None => client()?,
+            };
+            let path = path.map_or_else(|| input_path(year, day), Ok)?;
+            let bin = bin.unwrap_or_else(|| base_yd_path(year, day));
+            let bin = (!download_only).then_some(bin.as_str());
+            scaffold(&client, year, day, &path, bin).await?
+        }
+        Opt::Run {
+            year, all: true, ..
+        } => run_all(year.map(cleanup_year))?,
+        Opt::Verify { year, day } => verify(year.map(cleanup_year), day)?,
+        Opt::Submit {
+            year,
+            day,
+            part,
+            answer,
+        } => {
+            let year = year.map(cleanup_year).unwrap_or(recent_year);
+            let day = day.unwrap_or(recent_day);
+            submit(year, day, part, answer).await?
+        }
+        Opt::Guesses(GuessesOpt::List { year, day }) => {
             let year = year.map(cleanup_year).unwrap_or(recent_year);
             let day = day.unwrap_or(recent_day);
-            scaffold(year, day).await?
+            list_guesses(year, day)?
         }
-        Opt::Run { year, day } => {
+        Opt::Puzzle { year, day, refresh } => {
             let year = year.map(cleanup_year).unwrap_or(recent_year);
             let day = day.unwrap_or(recent_day);
-            run(year, day)?
+            save_puzzle(&client()?, year, day, refresh).await?
+        }
+        Opt::Remove { year, day, bin } => {
+            remove(&bin.unwrap_or_else(|| base_yd_path(cleanup_year(year), day)))?
+        }
+        Opt::Stats { year, offline } => stats(year.map(cleanup_year), offline).await?,
+        Opt::Wait { delay } => wait(&SystemClock, Duration::from_secs(delay)).await?,
+        Opt::Leaderboard { id, year } => {
+            let year = year.map(cleanup_year).unwrap_or(recent_year);
+            let cache = leaderboard::cache_path(year, id);
+            let leaderboard = Leaderboard::load(&client()?, year, id, &cache).await?;
+            print!("{}", leaderboard);
+        }
+        Opt::Run {
+            year,
+            day,
+            path,
+            bin,
+            ..
+        } => {
+            let year = year.map(cleanup_year).unwrap_or(recent_year);
+            let day = day.unwrap_or(recent_day);
+            let path = path.map_or_else(|| input_path(year, day), Ok)?;
+            run(&bin.unwrap_or_else(|| base_yd_path(year, day)), &path)?
         }
     }
     Ok(())
 }
-
-// #[tokio::main]
-// async fn main() -> Result<()> {
-//     let current = current_date();
-//     let base_path = PathBuf::from_str(CARGO_ROOT)?;
-//     match Opt::from_args() {
-//         Opt::Scaffold {
-//             year,
-//             day,
-//             path,
-//             config,
-//             download_only,
-//             bin,
-//         } => {
-//             let year = {
-//                 let mut year = year.unwrap_or_else(|| current.year());
-//                 // year provided in two digit format (21 => 2021)
-//                 if year < 100 {
-//                     year += 2000;
-//                 }
-//                 year
-//             };
-//             let day = day.unwrap_or_else(|| current.day());
-
-//             let path = path.unwrap_or_else(|| {
-//                 let mut path = base_path.clone();
-//                 path.push("inputs");
-//                 path.push(format!("y{:04}d{:02}.txt", year, day));
-//                 path
-//             });
-
-//             if !path.exists() {
-//                 let config = config.unwrap_or_else(|| {
-//                     let mut config = base_path.clone();
-//                     config.push("Advent.toml");
-//                     config
-//                 });
-//                 let config: Config = toml::from_str(&fs::read_to_string(config)?)?;
-
-//                 // not downloaded, do download
-//                 let response = Builder::default()
-//                     .build(HttpsConnector::new())
-//                     .request(
-//                         Request::builder()
-//                             .method(Method::GET)
-//                             .header(COOKIE, format!("session={}", config.config.session))
-//                             .uri(format!(
-//                                 "https://adventofcode.com/{}/day/{}/input",
-//                                 year, day
-//                             ))
-//                             .body(Body::empty())?,
-//                     )
-//                     .await?;
-
-//                 let mut dl_file = File::create(path)?;
-//                 let body = response.into_body();
-//                 let bytes = body::to_bytes(body).await?;
-//                 dl_file.write_all(&bytes)?;
-//             }
-
-//             if !download_only {
-//                 let bin_name = bin.unwrap_or_else(|| format!("y{:04}d{:02}", year, day));
-//                 let bin_name_rs = format!("{}.rs", bin_name);
-//                 let bin_path = {
-//                     let mut bin_path = base_path.clone();
-//                     bin_path.push("src");
-//                     bin_path.push(&bin_name_rs);
-//                     bin_path
-//                 };
-//                 if !bin_path.exists() {
-//                     // create new binary
-//                     let mut bin_file = File::create(bin_path)?;
-//                     bin_file.write_all(SCAFFOLD.trim().as_bytes())?;
-//                 }
-//                 let cargo_path = {
-//                     let mut cargo_path = base_path.clone();
-//                     cargo_path.push("Cargo.toml");
-//                     cargo_path
-//                 };
-//                 let current_cargo = fs::read_to_string(&cargo_path)?;
-//                 let mut cargo_config: Value = toml::from_str(&current_cargo)?;
-//                 let bins = cargo_config
-//                     .get_mut("bin")
-//                     .and_then(|v| v.as_array_mut())
-//                     .ok_or("No bins configured? You changed and broke something.")?;
-//                 if !bins
-//                     .iter()
-//                     .any(|v| v.get("name").and_then(|v| v.as_str()) == Some(&bin_name))
-//                 {
-//                     bins.push(Value::Table(Map::from_iter([
-//                         ("name".into(), Value::String(bin_name)),
-//                         ("path".into(), Value::String(format!("src/{}", bin_name_rs))),
-//                     ])))
-//                 }
-//                 let mut cargo_file = OpenOptions::new()
-//                     .read(true)
-//                     .write(true)
-//                     .create(false)
-//                     .truncate(true)
-//                     .open(cargo_path)?;
-
-//                 cargo_file.write_all(toml::to_string_pretty(&cargo_config)?.as_bytes())?;
-//             }
-//         }
-//         Opt::Run {
-//             year,
-//             day,
-//             path,
-//             bin,
-//         } => {
-//             let year = {
-//                 let mut year = year.unwrap_or_else(|| current.year());
-//                 // year provided in two digit format (21 => 2021)
-//                 if year < 100 {
-//                     year += 2000;
-//                 }
-//                 year
-//             };
-//             let day = day.unwrap_or_else(|| current.day());
-
-//             let path = path.unwrap_or_else(|| {
-//                 let mut path = base_path.clone();
-//                 path.push("inputs");
-//                 path.push(format!("y{:04}d{:02}.txt", year, day));
-//                 path
-//             });
-//             let bin_name = bin.unwrap_or_else(|| format!("y{:04}d{:02}", year, day));
-
-//             Command::new("cargo")
-//                 .arg("run")
-//                 .arg("--release")
-//                 .arg("--bin")
-//                 .arg(bin_name)
-//                 .arg("--")
-//                 .arg(path)
-//                 .spawn()?
-//                 .wait()?;
-//         }
-//         _ => {}
-//     }
-//     Ok(())
-// }
-
-// // Advent of code is based in UTC-5
-// fn current_date() -> NaiveDate {
-//     let mut date =
-//         TimeZone::timestamp_nanos(&FixedOffset::west(18_000), Utc::now().timestamp_nanos())
-//             .date()
-//             .naive_local();
-
-//     // it's not december yet
-//     if date.month() < 12 {
-//         date = date.with_year(date.year() - 1).unwrap();
-//     }
-
-//     // it's not advent of code days
-//     if date.day() > 25 {
-//         date = date.with_day(25).unwrap();
-//     }
-
-//     date
-// }
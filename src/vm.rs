@@ -0,0 +1,226 @@
+//! A virtual machine for the advent of code problems which interpret a small assembly language
+//!
+//! Each problem supplies its own instruction set by implementing [`Instruction`], while the
+//! [`Machine`] handles the registers, instruction pointer, tracing and loop detection.
+use std::collections::HashMap;
+
+/// Named registers, every register holds zero until it is written
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Registers {
+    values: HashMap<char, i64>,
+}
+
+impl Registers {
+    pub fn get(&self, register: char) -> i64 {
+        self.values.get(&register).copied().unwrap_or(0)
+    }
+
+    pub fn get_mut(&mut self, register: char) -> &mut i64 {
+        self.values.entry(register).or_insert(0)
+    }
+
+    pub fn set(&mut self, register: char, value: i64) {
+        self.values.insert(register, value);
+    }
+}
+
+/// Where execution continues after an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// Continue with the following instruction
+    Next,
+    /// Jump relative to the current instruction
+    Jump(i64),
+    /// Stop the program
+    Halt,
+}
+
+/// An instruction set which can be run by a [`Machine`]
+pub trait Instruction {
+    fn execute(&self, registers: &mut Registers) -> Flow;
+}
+
+/// When a [`Machine`] should stop running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// Only once the program has exited or halted
+    AtExit,
+    /// Also before any instruction would run a second time
+    OnLoop,
+}
+
+/// Why a [`Machine`] stopped running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// The instruction pointer left the program
+    Terminated,
+    /// An instruction halted the program
+    Halted,
+    /// An instruction was about to run a second time
+    Looped,
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine<I> {
+    program: Vec<I>,
+    registers: Registers,
+    pointer: i64,
+    visited: Vec<bool>,
+}
+
+impl<I: Instruction> Machine<I> {
+    pub fn new(program: Vec<I>) -> Machine<I> {
+        let visited = vec![false; program.len()];
+        Machine {
+            program,
+            registers: Registers::default(),
+            pointer: 0,
+            visited,
+        }
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut Registers {
+        &mut self.registers
+    }
+
+    /// The index of the next instruction to run, which may be outside the program once it exits
+    pub fn pointer(&self) -> i64 {
+        self.pointer
+    }
+
+    /// Returns to the start of the program with every register cleared
+    pub fn reset(&mut self) {
+        self.registers = Registers::default();
+        self.pointer = 0;
+        self.visited.fill(false);
+    }
+
+    fn current(&self) -> Option<usize> {
+        usize::try_from(self.pointer)
+            .ok()
+            .filter(|&pointer| pointer < self.program.len())
+    }
+
+    /// Runs a single instruction, returning why the program stopped if it did
+    pub fn step(&mut self) -> Option<Exit> {
+        let Some(pointer) = self.current() else {
+            return Some(Exit::Terminated);
+        };
+        self.visited[pointer] = true;
+        match self.program[pointer].execute(&mut self.registers) {
+            Flow::Next => self.pointer += 1,
+            Flow::Jump(offset) => self.pointer += offset,
+            Flow::Halt => return Some(Exit::Halted),
+        }
+        self.current().is_none().then_some(Exit::Terminated)
+    }
+
+    /// Runs until the program stops
+    pub fn run(&mut self, stop: Stop) -> Exit {
+        self.run_traced(stop, |_, _, _| {})
+    }
+
+    /// Runs until the program stops, calling `trace` with the pointer, instruction and registers
+    /// before each instruction is run
+    pub fn run_traced(&mut self, stop: Stop, mut trace: impl FnMut(usize, &I, &Registers)) -> Exit {
+        loop {
+            if let Some(pointer) = self.current() {
+                if stop == Stop::OnLoop && self.visited[pointer] {
+                    return Exit::Looped;
+                }
+                trace(pointer, &self.program[pointer], &self.registers);
+            }
+            if let Some(exit) = self.step() {
+                return exit;
+            }
+        }
+    }
+
+    /// Runs the program from the start with one instruction replaced, then restores the original
+    /// instruction, leaving the registers as the patched program left them
+    pub fn run_patched(&mut self, index: usize, instruction: I, stop: Stop) -> Exit {
+        let original = std::mem::replace(&mut self.program[index], instruction);
+        self.reset();
+        let exit = self.run(stop);
+        self.program[index] = original;
+        exit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tiny instruction set, counting down register `a` into register `b`
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Op {
+        Set(char, i64),
+        Inc(char, i64),
+        JumpIfPositive(char, i64),
+        Halt,
+    }
+
+    impl Instruction for Op {
+        fn execute(&self, registers: &mut Registers) -> Flow {
+            match *self {
+                Op::Set(register, value) => registers.set(register, value),
+                Op::Inc(register, value) => *registers.get_mut(register) += value,
+                Op::JumpIfPositive(register, offset) if registers.get(register) > 0 => {
+                    return Flow::Jump(offset)
+                }
+                Op::JumpIfPositive(_, _) => {}
+                Op::Halt => return Flow::Halt,
+            }
+            Flow::Next
+        }
+    }
+
+    fn countdown() -> Machine<Op> {
+        Machine::new(vec![
+            Op::Set('a', 3),
+            Op::Inc('b', 2),
+            Op::Inc('a', -1),
+            Op::JumpIfPositive('a', -2),
+        ])
+    }
+
+    #[test]
+    fn run_test() {
+        let mut machine = countdown();
+        let mut trace = Vec::new();
+        let exit = machine.run_traced(Stop::AtExit, |pointer, _, registers| {
+            trace.push((pointer, registers.get('a')))
+        });
+        assert_eq!(exit, Exit::Terminated);
+        assert_eq!(machine.registers().get('b'), 6);
+        assert_eq!(machine.pointer(), 4);
+        assert_eq!(trace.len(), 10);
+        assert_eq!(trace[..5], [(0, 0), (1, 3), (2, 3), (3, 2), (1, 2)]);
+
+        machine.reset();
+        assert_eq!(machine.run(Stop::OnLoop), Exit::Looped);
+        assert_eq!(machine.pointer(), 1);
+        assert_eq!(machine.registers().get('b'), 2);
+    }
+
+    #[test]
+    fn patch_test() {
+        let mut machine = countdown();
+        assert_eq!(machine.run_patched(2, Op::Halt, Stop::AtExit), Exit::Halted);
+        assert_eq!(machine.registers().get('b'), 2);
+        assert_eq!(machine.program()[2], Op::Inc('a', -1));
+
+        machine.reset();
+        assert_eq!(machine.step(), None);
+        assert_eq!(machine.run(Stop::AtExit), Exit::Terminated);
+        assert_eq!(machine.step(), Some(Exit::Terminated));
+    }
+}
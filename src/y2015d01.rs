@@ -4,7 +4,7 @@ struct ParsedInput {
     signals: Vec<i32>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         branch::alt,
         character::complete::char,
@@ -11,7 +11,7 @@ struct PresentBox {
     dim3: i32,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         character::complete::{char, i32, line_ending},
         combinator::map,
@@ -5,7 +5,7 @@ struct ParsedInput {
     directions: Vec<(i64, i64)>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         branch::alt,
         character::complete::char,
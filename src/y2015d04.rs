@@ -4,7 +4,7 @@ struct ParsedInput<'a> {
     key: &'a str,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
     use nom::{character::complete::alpha1, combinator::map};
     let mut parse = map(alpha1, |key| ParsedInput { key });
     parse(input)
@@ -13,7 +13,7 @@ fn parse(input: &str) -> ParseResult<ParsedInput> {
 fn mine(key: &str, zeroes: usize) -> Result<usize> {
     let lead = format!("{:0>pad$}", "", pad = zeroes);
     for i in 0.. {
-        if format!("{:x}", md5::compute(&format!("{}{}", key, i)))[..zeroes] == lead {
+        if format!("{:x}", md5::compute(format!("{}{}", key, i)))[..zeroes] == lead {
             return Ok(i);
         }
     }
@@ -5,7 +5,7 @@ struct ParsedInput<'a> {
     strings: Vec<&'a str>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
     use nom::{
         character::complete::{alpha1, line_ending},
         combinator::map,
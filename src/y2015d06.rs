@@ -1,5 +1,4 @@
-use aoc::*;
-use ndarray::{s, Array2, ArrayBase, Dim, ViewRepr};
+use aoc::{grid::Grid, *};
 
 struct ParsedInput {
     commands: Vec<Command>,
@@ -18,21 +17,20 @@ struct Command {
     to: (usize, usize),
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
+    use aoc::parsers::{coordinate, lines};
     use nom::{
         branch::alt,
         bytes::complete::tag,
-        character::complete::{char, line_ending, u32},
+        character::complete::u32,
         combinator::{map, value},
-        multi::separated_list1,
         sequence::{separated_pair, tuple},
     };
     let turn_on = value(Operation::TurnOn, tag("turn on "));
     let toggle = value(Operation::Toggle, tag("toggle "));
     let turn_off = value(Operation::TurnOff, tag("turn off "));
     let operation = alt((turn_on, toggle, turn_off));
-    let coord_u32 = |i| separated_pair(u32, char(','), u32)(i);
-    let coord_usize = |i| map(coord_u32, |(a, b)| (a as usize, b as usize))(i);
+    let coord_usize = |i| map(coordinate(u32), |(a, b)| (a as usize, b as usize))(i);
     let from_to = separated_pair(coord_usize, tag(" through "), coord_usize);
     let command_raw = tuple((operation, from_to));
     let command = map(command_raw, |(operation, (from, to))| Command {
@@ -40,30 +38,23 @@ fn parse(input: &str) -> ParseResult<ParsedInput> {
         from,
         to,
     });
-    let commands = separated_list1(line_ending, command);
+    let commands = lines(command);
     let mut parsed = map(commands, |commands| ParsedInput { commands });
     parsed(input)
 }
 
 struct LightSimulator {
-    lights: Array2<u32>,
+    lights: Grid<u32>,
 }
 impl LightSimulator {
     fn new() -> LightSimulator {
         LightSimulator {
-            lights: Array2::zeros((1000, 1000)),
+            lights: Grid::from_elem(1000, 1000, 0),
         }
     }
-    fn target_slice_mut(
-        &mut self,
-        from: (usize, usize),
-        to: (usize, usize),
-    ) -> ArrayBase<ViewRepr<&mut u32>, Dim<[usize; 2]>> {
-        self.lights.slice_mut(s![from.0..=to.0, from.1..=to.1])
-    }
     fn simulate_v1<'a>(&mut self, commands: impl IntoIterator<Item = &'a Command>) -> &mut Self {
         for command in commands {
-            let mut target = self.target_slice_mut(command.from, command.to);
+            let mut target = self.lights.region_mut(command.from, command.to);
             match command.operation {
                 Operation::TurnOn => target.fill(1),
                 Operation::Toggle => target.mapv_inplace(|i| i ^ 1),
@@ -74,7 +65,7 @@ impl LightSimulator {
     }
     fn simulate_v2<'a>(&mut self, commands: impl IntoIterator<Item = &'a Command>) -> &mut Self {
         for command in commands {
-            let mut target = self.target_slice_mut(command.from, command.to);
+            let mut target = self.lights.region_mut(command.from, command.to);
             match command.operation {
                 Operation::TurnOn => target.mapv_inplace(|i| i + 1),
                 Operation::Toggle => target.mapv_inplace(|i| i + 2),
@@ -84,7 +75,7 @@ impl LightSimulator {
         self
     }
     fn total_brightness(&self) -> u32 {
-        self.lights.sum()
+        self.lights.cells().sum()
     }
 }
 
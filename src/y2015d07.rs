@@ -1,6 +1,9 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+};
 
-use aoc::*;
+use aoc::{dot::Dot, *};
 
 #[derive(Debug)]
 struct ParsedInput<'a> {
@@ -23,18 +26,27 @@ enum GateIn<'a> {
     Not(Source<'a>),
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
     use nom::{
         branch::alt,
         bytes::complete::tag,
-        character::complete::{alpha1, line_ending, u16},
-        combinator::map,
+        bytes::complete::take_while1,
+        character::complete::line_ending,
+        combinator::{map, map_res},
         multi::separated_list1,
         sequence::{separated_pair, tuple},
     };
-    let number = |i| map(u16, Source::Number)(i);
-    let gate = |i| map(alpha1, Source::Wire)(i);
-    let source = |i| alt((gate, number))(i);
+    // wires may be named by any identifier which is not a number
+    let name = |i| take_while1(|c: char| c.is_alphanumeric() || c == '_')(i);
+    let source = |i| {
+        map_res(name, |name: &str| {
+            if name.chars().all(|c| c.is_ascii_digit()) {
+                name.parse().map(Source::Number)
+            } else {
+                Ok(Source::Wire(name))
+            }
+        })(i)
+    };
     let in_bind = map(source, GateIn::Bind);
     let in_and = map(
         separated_pair(source, tag(" AND "), source),
@@ -55,67 +67,275 @@ fn parse(input: &str) -> ParseResult<ParsedInput> {
     let in_not = map(tuple((tag("NOT "), source)), |(_, n)| GateIn::Not(n));
     let gate_in = alt((in_not, in_rshift, in_lshift, in_or, in_and, in_bind));
     let bind = tag(" -> ");
-    let binding = map(separated_pair(gate_in, bind, alpha1), |(a, b)| (b, a));
+    let binding = map(separated_pair(gate_in, bind, name), |(a, b)| (b, a));
     let bindings = separated_list1(line_ending, binding);
     let mut parse = map(bindings, |bindings| ParsedInput { bindings });
     parse(input)
 }
 
+impl<'a> GateIn<'a> {
+    fn sources(self) -> impl Iterator<Item = Source<'a>> {
+        let (first, second) = match self {
+            GateIn::Bind(a) | GateIn::Not(a) => (a, None),
+            GateIn::And(a, b) | GateIn::Or(a, b) | GateIn::RShift(a, b) | GateIn::LShift(a, b) => {
+                (a, Some(b))
+            }
+        };
+        std::iter::once(first).chain(second)
+    }
+
+    /// The wires this gate reads from
+    fn inputs(self) -> impl Iterator<Item = &'a str> {
+        self.sources().filter_map(|source| match source {
+            Source::Wire(wire) => Some(wire),
+            Source::Number(_) => None,
+        })
+    }
+
+    fn evaluate(self, value: impl Fn(Source<'a>) -> u16) -> u16 {
+        match self {
+            GateIn::Bind(a) => value(a),
+            GateIn::And(a, b) => value(a) & value(b),
+            GateIn::Or(a, b) => value(a) | value(b),
+            GateIn::RShift(a, b) => value(a).checked_shr(value(b).into()).unwrap_or(0),
+            GateIn::LShift(a, b) => value(a).checked_shl(value(b).into()).unwrap_or(0),
+            GateIn::Not(a) => !value(a),
+        }
+    }
+}
+
+impl fmt::Display for Source<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Wire(wire) => write!(f, "{}", wire),
+            Source::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+impl fmt::Display for GateIn<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateIn::Bind(a) => write!(f, "{}", a),
+            GateIn::And(a, b) => write!(f, "{} AND {}", a, b),
+            GateIn::Or(a, b) => write!(f, "{} OR {}", a, b),
+            GateIn::RShift(a, b) => write!(f, "{} RSHIFT {}", a, b),
+            GateIn::LShift(a, b) => write!(f, "{} LSHIFT {}", a, b),
+            GateIn::Not(a) => write!(f, "NOT {}", a),
+        }
+    }
+}
+
 #[derive(Default)]
 struct WireSystem<'a> {
     connections: HashMap<&'a str, GateIn<'a>>,
-    resolved_values: HashMap<&'a str, u16>,
 }
 impl<'a> FromIterator<(&'a str, GateIn<'a>)> for WireSystem<'a> {
     fn from_iter<T: IntoIterator<Item = (&'a str, GateIn<'a>)>>(iter: T) -> Self {
         WireSystem {
             connections: iter.into_iter().collect(),
-            resolved_values: HashMap::new(),
         }
     }
 }
 impl<'a> WireSystem<'a> {
-    fn resolve_source(&mut self, source: Source<'a>) -> Result<u16> {
-        Ok(match source {
-            Source::Wire(wire) => self.resolve_wire(wire)?,
-            Source::Number(number) => number,
+    /// Replaces whatever drives a wire with a fixed signal
+    fn override_wire(&mut self, wire: &'a str, value: u16) {
+        self.connections
+            .insert(wire, GateIn::Bind(Source::Number(value)));
+    }
+
+    /// Orders the given wires and every wire they read from, directly or not, such that every wire
+    /// comes after the wires it reads from
+    fn evaluation_order(&self, wires: &[&'a str]) -> Result<Vec<&'a str>> {
+        let mut needed = HashSet::new();
+        let mut stack = wires.to_vec();
+        while let Some(wire) = stack.pop() {
+            if !needed.insert(wire) {
+                continue;
+            }
+            for input in self.connections[wire].inputs() {
+                if !self.connections.contains_key(input) {
+                    return Err(format!("Wire {} reads from undefined wire {}", wire, input).into());
+                }
+                stack.push(input);
+            }
+        }
+        let mut readers: HashMap<&str, Vec<&str>> = HashMap::new();
+        for &wire in &needed {
+            for input in self.connections[wire].inputs() {
+                readers.entry(input).or_default().push(wire);
+            }
+        }
+        // sorted, so that the same cycle is reported every time
+        let mut wires = needed.into_iter().collect::<Vec<_>>();
+        wires.sort();
+        graph::topological_sort(wires, |wire| {
+            readers.get(wire).cloned().unwrap_or_default()
+        })
+        .map_err(|mut cycle| {
+            cycle.push(cycle[0]);
+            format!("Wires form a cycle: {}", cycle.join(" -> ")).into()
         })
     }
-    fn resolve_wire(&mut self, wire: &'a str) -> Result<u16> {
-        if let Some(&cache) = self.resolved_values.get(wire) {
-            return Ok(cache);
+
+    /// Resolves the signal on the given wires, and every wire they read from, sorted by wire name
+    fn evaluate_wires(&self, wires: &[&'a str]) -> Result<Signals<'a>> {
+        let mut values = BTreeMap::new();
+        for wire in self.evaluation_order(wires)? {
+            let value = self.connections[wire].evaluate(|source| match source {
+                Source::Wire(input) => values[input],
+                Source::Number(number) => number,
+            });
+            values.insert(wire, value);
         }
-        let value = match *self.connections.get(wire).ok_or("Unknown wire")? {
-            GateIn::Bind(a) => self.resolve_source(a)?,
-            GateIn::And(a, b) => self.resolve_source(a)? & self.resolve_source(b)?,
-            GateIn::Or(a, b) => self.resolve_source(a)? | self.resolve_source(b)?,
-            GateIn::RShift(a, b) => self.resolve_source(a)? >> self.resolve_source(b)?,
-            GateIn::LShift(a, b) => self.resolve_source(a)? << self.resolve_source(b)?,
-            GateIn::Not(a) => !self.resolve_source(a)?,
-        };
-        self.resolved_values.insert(wire, value);
-        Ok(value)
+        Ok(Signals(values))
+    }
+
+    /// Resolves the signal on every wire, sorted by wire name
+    fn evaluate(&self) -> Result<Signals<'a>> {
+        let mut wires = self.connections.keys().copied().collect::<Vec<_>>();
+        // sorted, so that the same undefined wire is reported every time
+        wires.sort();
+        self.evaluate_wires(&wires)
+    }
+
+    /// Resolves the signal on one wire, without resolving the wires it does not read from
+    fn resolve_wire(&self, wire: &str) -> Result<u16> {
+        let (&wire, _) = self
+            .connections
+            .get_key_value(wire)
+            .ok_or_else(|| format!("Wire {} is undefined", wire))?;
+        Ok(self.evaluate_wires(&[wire])?.0[wire])
+    }
+}
+
+/// Draws the circuit, with each gate as a box labelled by its expression between the wires it reads
+/// from and the wire it drives
+fn export_dot(input: &ParsedInput) -> Result<Dot> {
+    let wires = WireSystem::from_iter(input.bindings.iter().copied());
+    let mut dot = Dot::directed();
+    let mut names = wires.connections.keys().collect::<Vec<_>>();
+    names.sort();
+    for wire in names {
+        dot.node(wire, vec![("shape", "ellipse".to_string())]);
+        match wires.connections[wire] {
+            GateIn::Bind(Source::Wire(input)) => {
+                dot.edge(input, wire, vec![]);
+            }
+            gate => {
+                let id = format!("{} gate", wire);
+                let attributes = vec![("shape", "box".to_string()), ("label", gate.to_string())];
+                dot.node(&id, attributes).edge(&id, wire, vec![]);
+                for input in gate.inputs() {
+                    dot.edge(input, &id, vec![]);
+                }
+            }
+        }
+    }
+    Ok(dot)
+}
+
+/// The resolved signal on every wire, which displays as one wire per line
+#[derive(Debug)]
+struct Signals<'a>(BTreeMap<&'a str, u16>);
+
+impl fmt::Display for Signals<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (wire, value) in &self.0 {
+            writeln!(f, "{}: {}", wire, value)?;
+        }
+        Ok(())
     }
 }
 
+/// The signal on every wire of the circuit, for `--dump`
+fn signals<'a>(input: &ParsedInput<'a>) -> Result<Signals<'a>> {
+    WireSystem::from_iter(input.bindings.iter().copied()).evaluate()
+}
+
+/// The wire whose signal is the answer to both tasks
+const OUTPUT_WIRE: &str = "a";
+/// The wire which is overridden with the answer to task 1 for task 2
+const OVERRIDDEN_WIRE: &str = "b";
+
 fn task1(input: &ParsedInput) -> Result<u16> {
-    WireSystem::from_iter(input.bindings.iter().copied()).resolve_wire("a")
+    WireSystem::from_iter(input.bindings.iter().copied()).resolve_wire(OUTPUT_WIRE)
 }
 
 fn task2(input: &ParsedInput, task1: u16) -> Result<u16> {
-    WireSystem::from_iter(input.bindings.iter().map(|&(gate, input)| {
-        if gate == "b" {
-            (gate, GateIn::Bind(Source::Number(task1)))
-        } else {
-            (gate, input)
-        }
-    }))
-    .resolve_wire("a")
+    let mut wires = WireSystem::from_iter(input.bindings.iter().copied());
+    wires.override_wire(OVERRIDDEN_WIRE, task1);
+    wires.resolve_wire(OUTPUT_WIRE)
 }
 
 #[test]
 fn test() {
-    assert_task!(parse, task1, "123 -> x\n456 -> y\nx AND y -> a", 72)
+    assert_task!(parse, task1, "123 -> x\n456 -> y\nx AND y -> a", 72);
+
+    let wires = |input| {
+        let parsed: ParsedInput = parse_all(input, parse(input)).unwrap();
+        WireSystem::from_iter(parsed.bindings)
+    };
+    let input = "
+123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i
+    "
+    .trim();
+    let values = wires(input).evaluate().unwrap();
+    assert_eq!(
+        values.to_string(),
+        "d: 72\ne: 507\nf: 492\ng: 114\nh: 65412\ni: 65079\nx: 123\ny: 456\n"
+    );
+    assert_eq!(dump("123 -> x\nNOT x -> y").unwrap(), "x: 123\ny: 65412\n");
+    let mut overridden = wires(input);
+    overridden.override_wire("y", 1);
+    overridden.override_wire("z9", 7);
+    let values = overridden.evaluate().unwrap();
+    assert_eq!((values.0["d"], values.0["g"], values.0["z9"]), (1, 0, 7));
+
+    let error = wires("1 -> a\na AND c2 -> b").evaluate().unwrap_err();
+    assert_eq!(error.to_string(), "Wire b reads from undefined wire c2");
+    let error = wires("x -> y\ny OR w -> x\n1 -> w\nx -> a")
+        .evaluate()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Wires form a cycle: x -> y -> x");
+    // only the wires a wire reads from need to be resolvable to resolve it
+    let broken = wires("x -> y\ny -> x\nz AND q -> w\n1 -> b\nb LSHIFT 3 -> a");
+    assert_eq!(broken.resolve_wire("a").unwrap(), 8);
+    assert_eq!(
+        broken.resolve_wire("w").unwrap_err().to_string(),
+        "Wire w reads from undefined wire z"
+    );
+    assert_eq!(
+        broken.resolve_wire("c").unwrap_err().to_string(),
+        "Wire c is undefined"
+    );
+
+    let input = "123 -> x\nx -> y\nx LSHIFT 2 -> a";
+    let graph = export_dot(&parse_all(input, parse(input)).unwrap()).unwrap();
+    assert_eq!(
+        graph.to_string(),
+        r#"digraph {
+    "a" [shape="ellipse"];
+    "a gate" [shape="box", label="x LSHIFT 2"];
+    "a gate" -> "a";
+    "x" -> "a gate";
+    "x" [shape="ellipse"];
+    "x gate" [shape="box", label="123"];
+    "x gate" -> "x";
+    "y" [shape="ellipse"];
+    "x" -> "y";
+}
+"#
+    );
 }
 
 aoc_main!(parse, task1 -> task2);
+aoc_dot!(parse, export_dot);
+aoc_dump!(parse, signals);
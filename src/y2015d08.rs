@@ -9,7 +9,7 @@ struct ParsedInput<'a> {
     inputs: Vec<Record<'a>>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
     use nom::{
         branch::alt,
         bytes::complete::{escaped_transform, take},
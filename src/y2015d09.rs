@@ -1,7 +1,4 @@
-use std::collections::{HashMap, HashSet};
-
-use aoc::*;
-use itertools::Itertools;
+use aoc::{graph::Graph, tsp, *};
 
 struct ParsedInput<'a> {
     routes: Vec<Route<'a>>,
@@ -12,7 +9,7 @@ struct Route<'a> {
     distance: u32,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
     use nom::{
         bytes::complete::tag,
         character::complete::{alpha1, line_ending, u32},
@@ -31,62 +28,23 @@ fn parse(input: &str) -> ParseResult<ParsedInput> {
     parsed(input)
 }
 
-fn task1(input: &ParsedInput) -> Result<u32> {
-    let destinations = input
-        .routes
-        .iter()
-        .map(|route| route.from)
-        .chain(input.routes.iter().map(|route| route.to))
-        .collect::<HashSet<_>>();
-    let adjacency_map = input
-        .routes
-        .iter()
-        .map(|route| (route.from, route.distance))
-        .zip(input.routes.iter().map(|route| route.to))
-        .flat_map(|((a, c), b)| [((a, b), c), ((b, a), c)])
-        .collect::<HashMap<(&str, &str), u32>>();
+/// The distances between every pair of destinations
+fn distances<'a>(input: &ParsedInput<'a>) -> Graph<&'a str, u32> {
+    let mut graph = Graph::new();
+    for route in &input.routes {
+        graph.add_undirected_edge(route.from, route.to, route.distance);
+    }
+    graph
+}
 
-    destinations
-        .iter()
-        .permutations(destinations.len())
-        .map(|routes| {
-            routes
-                .iter()
-                .tuple_windows()
-                .map(|(a, b)| adjacency_map.get(&(a, b)).copied().unwrap())
-                .sum::<u32>()
-        })
-        .min()
-        .ok_or_else(|| "No routes provided".into())
+fn task1(input: &ParsedInput) -> Result<u32> {
+    let (_, distance) = tsp::shortest_path(&distances(input))?.ok_or("No routes provided")?;
+    Ok(distance)
 }
 
 fn task2(input: &ParsedInput) -> Result<u32> {
-    let destinations = input
-        .routes
-        .iter()
-        .map(|route| route.from)
-        .chain(input.routes.iter().map(|route| route.to))
-        .collect::<HashSet<_>>();
-    let adjacency_map = input
-        .routes
-        .iter()
-        .map(|route| (route.from, route.distance))
-        .zip(input.routes.iter().map(|route| route.to))
-        .flat_map(|((a, c), b)| [((a, b), c), ((b, a), c)])
-        .collect::<HashMap<(&str, &str), u32>>();
-
-    destinations
-        .iter()
-        .permutations(destinations.len())
-        .map(|routes| {
-            routes
-                .iter()
-                .tuple_windows()
-                .map(|(a, b)| adjacency_map.get(&(a, b)).copied().unwrap())
-                .sum::<u32>()
-        })
-        .max()
-        .ok_or_else(|| "No routes provided".into())
+    let (_, distance) = tsp::longest_path(&distances(input))?.ok_or("No routes provided")?;
+    Ok(distance)
 }
 
 #[test]
@@ -4,7 +4,7 @@ struct ParsedInput<'a> {
     numbers: &'a str,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
     Ok((
         "",
         ParsedInput {
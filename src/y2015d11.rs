@@ -5,7 +5,7 @@ struct ParsedInput {
     input: Vec<u8>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     Ok((
         "",
         ParsedInput {
@@ -5,7 +5,7 @@ struct ParsedInput {
     json: JsonValue,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     Ok((
         "",
         ParsedInput {
@@ -35,21 +35,20 @@ fn collapse_sum_no_red(value: &JsonValue) -> Option<i64> {
         JsonValue::Bool(_) => Some(0),
         JsonValue::Number(n) => Some(n.as_i64().unwrap_or_default()),
         JsonValue::String(_) => Some(0),
-        JsonValue::Array(a) => a
-            .iter()
-            .map(collapse_sum_no_red)
-            .fold(Some(0), |acc, item| {
-                Some(acc.unwrap_or_default() + item.unwrap_or_default())
-            }),
+        JsonValue::Array(a) => Some(
+            a.iter()
+                .map(|item| collapse_sum_no_red(item).unwrap_or_default())
+                .sum(),
+        ),
         JsonValue::Object(o) => {
             if o.values().any(|v| v.as_str() == Some("red")) {
                 None
             } else {
-                o.values()
-                    .map(collapse_sum_no_red)
-                    .fold(Some(0), |acc, item| {
-                        Some(acc.unwrap_or_default() + item.unwrap_or_default())
-                    })
+                Some(
+                    o.values()
+                        .map(|item| collapse_sum_no_red(item).unwrap_or_default())
+                        .sum(),
+                )
             }
         }
     }
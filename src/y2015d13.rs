@@ -1,12 +1,15 @@
-use aoc::*;
-use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use aoc::{
+    graph::Graph,
+    tsp::{self, Goal, Tour},
+    *,
+};
+use std::collections::HashMap;
 
 struct ParsedInput<'a> {
     happiness_map: HashMap<(&'a str, &'a str), i64>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
     use nom::{
         branch::alt,
         bytes::complete::tag,
@@ -40,58 +43,31 @@ fn parse(input: &str) -> ParseResult<ParsedInput> {
     parse(input)
 }
 
-fn get_all_names<'a>(map: &HashMap<(&'a str, &'a str), i64>) -> HashSet<&'a str> {
-    map.keys()
-        .map(|(a, _)| *a)
-        .chain(map.keys().map(|(_, b)| *b))
-        .collect()
+/// Finds the happiest seating around the table, optionally including yourself
+fn happiest_arrangement(input: &ParsedInput, include_yourself: bool) -> Result<i64> {
+    let mut people = Graph::new();
+    for (&(a, b), &happiness) in &input.happiness_map {
+        people.add_edge(a, b, happiness);
+    }
+    // you are seated as an extra person after everyone else, who nobody minds sitting next to
+    let len = people.len() + usize::from(include_yourself);
+    let happiness = |a, b| {
+        if a == people.len() || b == people.len() {
+            return Some(0);
+        }
+        Some(people.weight(a, b).unwrap_or(0) + people.weight(b, a).unwrap_or(0))
+    };
+    let (_, happiness) = tsp::held_karp(len, happiness, Goal::Longest, Tour::Cycle)?
+        .ok_or("No people supplied")?;
+    Ok(happiness)
 }
 
 fn task1(input: &ParsedInput) -> Result<i64> {
-    let names = get_all_names(&input.happiness_map);
-    names
-        .iter()
-        .permutations(names.len())
-        .map(|arangement| {
-            arangement
-                .iter()
-                .cycle()
-                .take(arangement.len() + 1)
-                .tuple_windows()
-                .filter_map(|(a, b)| {
-                    Some(input.happiness_map.get(&(a, b))? + input.happiness_map.get(&(b, a))?)
-                })
-                .sum()
-        })
-        .max()
-        .ok_or_else(|| "No people supplied".into())
+    happiest_arrangement(input, false)
 }
 
 fn task2(input: &ParsedInput) -> Result<i64> {
-    let names = get_all_names(&input.happiness_map);
-
-    names
-        .iter()
-        .cloned()
-        .chain(Some(""))
-        .permutations(names.len() + 1)
-        .map(|arangement| {
-            arangement
-                .iter()
-                .cycle()
-                .take(arangement.len() + 1)
-                .tuple_windows()
-                .filter_map(|(a, b)| {
-                    if a.is_empty() || b.is_empty() {
-                        Some(0)
-                    } else {
-                        Some(input.happiness_map.get(&(a, b))? + input.happiness_map.get(&(b, a))?)
-                    }
-                })
-                .sum()
-        })
-        .max()
-        .ok_or_else(|| "No people supplied".into())
+    happiest_arrangement(input, true)
 }
 
 #[test]
@@ -5,7 +5,7 @@ struct ParsedInput {
     digits: Vec<u8>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         bytes::complete::take,
         character::complete::u8,
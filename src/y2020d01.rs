@@ -5,7 +5,7 @@ struct ParsedInput {
     records: Vec<i32>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         character::complete::{i32, line_ending},
         combinator::map,
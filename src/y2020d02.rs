@@ -11,7 +11,7 @@ struct Password<'a> {
     param2: usize,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
     use nom::{
         bytes::complete::tag,
         character::complete::{alpha1, char, digit1, line_ending, satisfy},
@@ -4,18 +4,19 @@ struct ParsedInput1<'a> {
     passports: Vec<Vec<(&'a str, &'a str)>>,
 }
 
-fn parse1(input: &str) -> ParseResult<ParsedInput1> {
+fn parse1(input: &str) -> ParseResult<'_, ParsedInput1<'_>> {
+    use aoc::parsers::blocks;
     use nom::{
         bytes::complete::take_till,
-        character::complete::{alpha1, char, line_ending, satisfy},
+        character::complete::{alpha1, char, satisfy},
         combinator::map,
         multi::separated_list1,
-        sequence::{separated_pair, tuple},
+        sequence::separated_pair,
     };
 
     let field = separated_pair(alpha1, char(':'), take_till(char::is_whitespace));
     let passport = separated_list1(satisfy(char::is_whitespace), field);
-    let passports = separated_list1(tuple((line_ending, line_ending)), passport);
+    let passports = blocks(passport);
     let mut parse = map(passports, |passports| ParsedInput1 { passports });
     parse(input)
 }
@@ -60,6 +61,7 @@ enum EyeColor {
     Other,
 }
 
+#[allow(dead_code)]
 #[derive(Copy, Clone)]
 enum Height {
     Cm(u32),
@@ -78,6 +80,7 @@ struct PassportBuilder {
     unrecognised_met: bool,
 }
 
+#[allow(dead_code)]
 #[derive(Copy, Clone)]
 enum FieldSet<'a> {
     BirthYear(u32),
@@ -133,7 +136,8 @@ impl PassportBuilder {
     }
 }
 
-fn parse2(input: &str) -> ParseResult<ParsedInput2> {
+fn parse2(input: &str) -> ParseResult<'_, ParsedInput2> {
+    use aoc::parsers::blocks;
     use nom::{
         branch::alt,
         bytes::complete::{tag, take_till1, take_while_m_n},
@@ -224,12 +228,9 @@ fn parse2(input: &str) -> ParseResult<ParsedInput2> {
             builder.build()
         },
     );
-    let mut parse = map(
-        separated_list1(tuple((line_ending, line_ending)), passport),
-        |l| ParsedInput2 {
-            passports: l.into_iter().flatten().collect(),
-        },
-    );
+    let mut parse = map(blocks(passport), |l| ParsedInput2 {
+        passports: l.into_iter().flatten().collect(),
+    });
     parse(input)
 }
 
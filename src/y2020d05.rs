@@ -23,7 +23,7 @@ enum PartitionH {
     Right,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         branch::alt,
         character::complete::{char, line_ending},
@@ -11,23 +11,14 @@ struct Person {
     answers: BTreeSet<char>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
-    use nom::{
-        character::complete::{alpha1, line_ending},
-        combinator::map,
-        multi::separated_list1,
-        sequence::tuple,
-    };
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
+    use aoc::parsers::{blocks, lines};
+    use nom::{character::complete::alpha1, combinator::map};
     let person = map(alpha1, |answers: &str| Person {
         answers: answers.chars().collect(),
     });
-    let group = map(separated_list1(line_ending, person), |members| Group {
-        members,
-    });
-    let mut parse = map(
-        separated_list1(tuple((line_ending, line_ending)), group),
-        |groups| ParsedInput { groups },
-    );
+    let group = map(lines(person), |members| Group { members });
+    let mut parse = map(blocks(group), |groups| ParsedInput { groups });
     parse(input)
 }
 
@@ -1,28 +1,31 @@
-use aoc::*;
-use std::collections::{HashMap, HashSet};
+use aoc::{
+    graph::{flood_fill, Graph},
+    *,
+};
 
 struct ParsedInput<'a> {
     policy: Policy<'a>,
 }
 
+/// Which bags must be directly contained by each bag, weighted by how many of them
 struct Policy<'a> {
-    rules: HashMap<Bag<'a>, Vec<(u32, Bag<'a>)>>,
+    rules: Graph<Bag<'a>, u32>,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 struct Bag<'a> {
     property: &'a str,
     color: &'a str,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
     use nom::{
         branch::alt,
         bytes::complete::tag,
         character::complete::{alpha1, char, line_ending, u32},
         combinator::{map, value},
         multi::separated_list1,
-        sequence::{separated_pair, tuple},
+        sequence::{separated_pair, terminated},
     };
     let bag = |i| {
         map(
@@ -35,29 +38,29 @@ fn parse(input: &str) -> ParseResult<ParsedInput> {
     let nobag = value(vec![], tag("no other"));
     let baglist = alt((separated_list1(baglist_sep, numbag), nobag));
     let rule_sep = tag(" bags contain ");
-    let rule = separated_pair(bag, rule_sep, baglist);
-    let rule_end = tuple((alt((tag(" bag."), tag(" bags."))), line_ending));
-    let policy = map(separated_list1(rule_end, rule), |rules| Policy {
-        rules: rules.into_iter().collect(),
+    let rule_end = alt((tag(" bag."), tag(" bags.")));
+    let rule = terminated(separated_pair(bag, rule_sep, baglist), rule_end);
+    let policy = map(separated_list1(line_ending, rule), |rules| {
+        let mut graph = Graph::new();
+        for (bag, contents) in rules {
+            graph.add_node(bag);
+            for (count, contained) in contents {
+                graph.add_edge(bag, contained, count);
+            }
+        }
+        Policy { rules: graph }
     });
     let mut parsed = map(policy, |policy| ParsedInput { policy });
     parsed(input)
 }
 
-fn bags_containing<'a>(policy: &Policy<'a>, contained_bag: Bag) -> HashSet<Bag<'a>> {
-    policy
-        .rules
-        .iter()
-        .fold(HashSet::new(), |mut bags, (&bag, contained_bags)| {
-            if contained_bags
-                .iter()
-                .any(|&(_, search)| search == contained_bag)
-            {
-                bags.insert(bag);
-                bags.extend(bags_containing(policy, bag).iter());
-            }
-            bags
-        })
+fn bags_containing(policy: &Policy, contained_bag: Bag) -> usize {
+    let Some(contained_bag) = policy.rules.id(&contained_bag) else {
+        return 0;
+    };
+    let containers = policy.rules.reversed();
+    // the contained bag is found by the flood fill, but does not contain itself
+    flood_fill(contained_bag, |&bag| containers.successors(bag)).len() - 1
 }
 
 const SHINY_GOLD_BAG: Bag<'static> = Bag {
@@ -66,21 +69,28 @@ const SHINY_GOLD_BAG: Bag<'static> = Bag {
 };
 
 fn task1(input: &ParsedInput) -> Result<usize> {
-    Ok(bags_containing(&input.policy, SHINY_GOLD_BAG).len())
+    Ok(bags_containing(&input.policy, SHINY_GOLD_BAG))
 }
 
-fn count_bags_contained(policy: &Policy, container_bag: Bag) -> u32 {
-    policy
-        .rules
-        .get(&container_bag)
-        .iter()
-        .flat_map(|n| n.iter())
-        .map(|&(numof, bag)| (count_bags_contained(policy, bag) + 1) * numof)
-        .sum()
+fn count_bags_contained(policy: &Policy, container_bag: Bag) -> Result<u32> {
+    let rules = &policy.rules;
+    let container_bag = rules.id(&container_bag).ok_or("Unknown bag")?;
+    let order = rules
+        .topological_sort()
+        .map_err(|cycle| format!("Bag {:?} contains itself", rules.node(cycle[0])))?;
+    // bags are counted innermost first, so everything a bag contains is counted before it
+    let mut contained = vec![0; rules.len()];
+    for &bag in order.iter().rev() {
+        contained[bag] = rules
+            .edges(bag)
+            .map(|(inner, count)| (contained[inner] + 1) * count)
+            .sum();
+    }
+    Ok(contained[container_bag])
 }
 
 fn task2(input: &ParsedInput) -> Result<u32> {
-    Ok(count_bags_contained(&input.policy, SHINY_GOLD_BAG))
+    count_bags_contained(&input.policy, SHINY_GOLD_BAG)
 }
 
 #[test]
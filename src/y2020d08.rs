@@ -1,4 +1,7 @@
-use aoc::*;
+use aoc::{
+    vm::{self, Exit, Flow, Machine, Registers, Stop},
+    *,
+};
 
 #[derive(Debug)]
 struct ParsedInput {
@@ -18,7 +21,7 @@ enum Operation {
     NoOp,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         branch::alt,
         bytes::complete::tag,
@@ -43,66 +46,63 @@ fn parse(input: &str) -> ParseResult<ParsedInput> {
     parse(input)
 }
 
-#[derive(Default)]
-struct Computer {
-    accumulator: i64,
-    instruction_pointer: i64,
-    trace: Vec<i64>,
-}
-impl Computer {
-    fn eval_without_loop(instructions: &[Instruction]) -> std::result::Result<i64, i64> {
-        let mut computer = Computer::default();
-        while let Some(instruction) = instructions.get(computer.instruction_pointer as usize) {
-            if computer.detect_loop() {
-                return Err(computer.accumulator);
-            }
-            computer.step(instruction);
-        }
-        Ok(computer.accumulator)
-    }
-    fn step(&mut self, instruction: &Instruction) {
-        self.trace.push(self.instruction_pointer);
-        match instruction.operation {
-            Operation::Accumulator => self.accumulator += instruction.argument,
-            Operation::Jump => self.instruction_pointer += instruction.argument - 1,
+/// The register holding the accumulator
+const ACCUMULATOR: char = 'a';
+
+impl vm::Instruction for Instruction {
+    fn execute(&self, registers: &mut Registers) -> Flow {
+        match self.operation {
+            Operation::Accumulator => *registers.get_mut(ACCUMULATOR) += self.argument,
+            Operation::Jump => return Flow::Jump(self.argument),
             Operation::NoOp => {}
         }
-        self.instruction_pointer += 1;
-    }
-    fn detect_loop(&self) -> bool {
-        self.trace.contains(&self.instruction_pointer)
+        Flow::Next
     }
 }
 
 fn task1(input: &ParsedInput) -> Result<i64> {
-    Ok(Computer::eval_without_loop(&input.instructions)
-        .err()
-        .ok_or("unexpected normal termination")?)
+    let mut machine = Machine::new(input.instructions.clone());
+    match machine.run(Stop::OnLoop) {
+        Exit::Looped => Ok(machine.registers().get(ACCUMULATOR)),
+        _ => Err("unexpected normal termination".into()),
+    }
 }
 
 fn task2(input: &ParsedInput) -> Result<i64> {
-    Ok(input
-        .instructions
-        .iter()
-        .enumerate()
-        .filter_map(|(i, instruction)| {
-            let mut candidate = input.instructions.clone();
-            candidate[i] = Instruction {
-                operation: match instruction.operation {
-                    Operation::Accumulator => return None,
-                    Operation::Jump => Operation::NoOp,
-                    Operation::NoOp => Operation::Jump,
-                },
-                argument: instruction.argument,
-            };
-            Some(candidate)
-        })
-        .filter_map(|instructions| Computer::eval_without_loop(&instructions).ok())
-        .next()
-        .ok_or("No solution available")?)
+    let mut machine = Machine::new(input.instructions.clone());
+    for (i, &instruction) in input.instructions.iter().enumerate() {
+        let operation = match instruction.operation {
+            Operation::Accumulator => continue,
+            Operation::Jump => Operation::NoOp,
+            Operation::NoOp => Operation::Jump,
+        };
+        let patch = Instruction {
+            operation,
+            ..instruction
+        };
+        if machine.run_patched(i, patch, Stop::OnLoop) == Exit::Terminated {
+            return Ok(machine.registers().get(ACCUMULATOR));
+        }
+    }
+    Err("No solution available".into())
 }
 
 #[test]
-fn test() {}
+fn test() {
+    let input = "
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+    "
+    .trim();
+    assert_task!(parse, task1, input, 5);
+    assert_task!(parse, task2, input, 8);
+}
 
 aoc_main!(parse, task1, task2);
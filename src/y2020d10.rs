@@ -7,7 +7,7 @@ struct ParsedInput {
     jolts: Vec<i64>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         character::complete::{i64, line_ending},
         combinator::map,
@@ -30,17 +30,13 @@ fn task1(input: &ParsedInput) -> Result<i64> {
         .iter()
         .tuple_windows()
         .map(|(a, b)| b - a)
-        .fold(Ok((0, 0, 0)), |acc, item| {
-            if let Ok((one, two, three)) = acc {
-                Ok(match item {
-                    1 => (one + 1, two, three),
-                    2 => (one, two + 1, three),
-                    3 => (one, two, three + 1),
-                    _ => return Err("unexpected skip of four jolts"),
-                })
-            } else {
-                acc
-            }
+        .try_fold((0, 0, 0), |(one, two, three), item| {
+            Ok::<_, &str>(match item {
+                1 => (one + 1, two, three),
+                2 => (one, two + 1, three),
+                3 => (one, two, three + 1),
+                _ => return Err("unexpected skip of four jolts"),
+            })
         })
         .map(|(one, _, three)| one * three)?)
 }
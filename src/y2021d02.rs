@@ -18,7 +18,7 @@ enum InstructionKind {
     Down,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         branch::alt,
         bytes::complete::tag,
@@ -6,7 +6,7 @@ struct ParsedInput {
     data: Vec<u32>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         character::complete::{digit1, line_ending},
         combinator::{map, map_res},
@@ -15,7 +15,7 @@ struct BingoBoard {
     marked: Array2<bool>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         character::complete::{char, line_ending, space0, space1, u32},
         combinator::{map, map_res},
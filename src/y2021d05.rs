@@ -1,7 +1,6 @@
 use aoc::*;
 use itertools::Itertools;
 use ndarray::Array2;
-use nom::multi::separated_list1;
 
 struct ParsedInput {
     lines: Vec<Line>,
@@ -19,15 +18,11 @@ struct Coord {
     y: usize,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
-    use nom::{
-        bytes::complete::tag,
-        character::complete::{char, line_ending, u32},
-        sequence::separated_pair,
-        Parser,
-    };
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
+    use aoc::parsers::{coordinate, lines};
+    use nom::{bytes::complete::tag, character::complete::u32, sequence::separated_pair, Parser};
     let coord = |i| {
-        separated_pair(u32, char(','), u32)
+        coordinate(u32)
             .map(|(x, y)| Coord {
                 x: x as usize,
                 y: y as usize,
@@ -42,7 +37,7 @@ fn parse(input: &str) -> ParseResult<ParsedInput> {
             .unwrap();
         Line { a, b }
     });
-    let mut parser = separated_list1(line_ending, line).map(|lines| ParsedInput { lines });
+    let mut parser = lines(line).map(|lines| ParsedInput { lines });
     parser.parse(input)
 }
 
@@ -51,7 +46,7 @@ fn find_field_overlaps(lines: &[Line]) -> usize {
         .iter()
         .map(|line| (line.a.x.max(line.b.x), line.a.y.max(line.b.y)))
         .fold((0, 0), |(mx, my), (x, y)| (mx.max(x), my.max(y)));
-    let mut field = Array2::from_elem((bound_x as usize + 1, bound_y as usize + 1), 0);
+    let mut field = Array2::from_elem((bound_x + 1, bound_y + 1), 0);
     for line in lines {
         let mut x_pos: Box<dyn Iterator<Item = usize>> = {
             if line.a.x <= line.b.x {
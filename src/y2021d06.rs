@@ -6,14 +6,10 @@ struct ParsedInput {
     fish_timings: BTreeMap<u32, usize>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
-    use nom::{
-        character::complete::{char, u32},
-        combinator::map,
-        multi::separated_list1,
-        Parser,
-    };
-    let mut parser = map(separated_list1(char(','), u32), |fish_timings| {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
+    use aoc::parsers::comma_separated;
+    use nom::{character::complete::u32, combinator::map, Parser};
+    let mut parser = map(comma_separated(u32), |fish_timings| {
         ParsedInput {
             fish_timings: fish_timings
                 .iter()
@@ -4,14 +4,10 @@ struct ParsedInput {
     crab_places: Vec<i64>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
-    use nom::{
-        character::complete::{char, i64},
-        combinator::map,
-        multi::separated_list1,
-        Parser,
-    };
-    let mut parser = map(separated_list1(char(','), i64), |crab_places| ParsedInput {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
+    use aoc::parsers::comma_separated;
+    use nom::{character::complete::i64, combinator::map, Parser};
+    let mut parser = map(comma_separated(i64), |crab_places| ParsedInput {
         crab_places,
     });
     parser.parse(input)
@@ -28,7 +24,6 @@ fn task2(input: &ParsedInput) -> Result<i64> {
     let positions = input.crab_places.clone();
     let max = *input.crab_places.iter().max().ok_or("no max found")?;
     Ok((0..max)
-        .into_iter()
         .map(|target| {
             positions
                 .iter()
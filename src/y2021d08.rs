@@ -30,7 +30,7 @@ enum SegmentWire {
     G = 6,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         bytes::complete::tag,
         character::complete::{char, line_ending},
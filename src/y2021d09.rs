@@ -1,29 +1,14 @@
-use aoc::*;
+use aoc::{graph::flood_fill, grid::Grid, *};
 use itertools::Itertools;
-use ndarray::{Array1, Array2, ArrayViewMut2};
 
 struct ParsedInput {
-    map: Array2<u8>,
+    map: Grid<u8>,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
-    use nom::{
-        bytes::complete::take,
-        character::complete::{line_ending, u8},
-        combinator::map_res,
-        multi::{many1, separated_list1},
-        Parser,
-    };
-    let height = take(1_usize).and_then(u8);
-    let row = many1(height);
-    let rows = separated_list1(line_ending, row);
-    let mut parser = map_res(rows, |rows| {
-        let height = rows.len();
-        let width = rows.get(0).map(|n| n.len()).unwrap_or_default();
-        Array1::from_iter(rows.into_iter().flatten())
-            .into_shape((height, width))
-            .map(|map| ParsedInput { map })
-    });
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
+    use aoc::parsers::digit;
+    use nom::{combinator::map, Parser};
+    let mut parser = map(Grid::parser(digit), |map| ParsedInput { map });
     parser.parse(input)
 }
 
@@ -40,11 +25,11 @@ fn task1(input: &ParsedInput) -> Result<Task1Output> {
             input
                 .map
                 .indexed_iter()
-                .filter(|&((y, x), &e)| {
-                    (y == 0 || e < input.map.get((y - 1, x)).copied().unwrap_or(u8::MAX))
-                        && e < input.map.get((y + 1, x)).copied().unwrap_or(u8::MAX)
-                        && (x == 0 || e < input.map.get((y, x - 1)).copied().unwrap_or(u8::MAX))
-                        && e < input.map.get((y, x + 1)).copied().unwrap_or(u8::MAX)
+                .filter(|&(position, &e)| {
+                    input
+                        .map
+                        .neighbours(position)
+                        .all(|neighbour| e < input.map[neighbour])
                 })
                 .map(|((y, x), &n)| ((y, x), n as u32 + 1))
                 .collect(),
@@ -52,39 +37,16 @@ fn task1(input: &ParsedInput) -> Result<Task1Output> {
     })
 }
 
-fn dfssize(map: &mut ArrayViewMut2<'_, bool>, (cy, cx): (usize, usize), sum: &mut usize) {
-    let target = if let Some(target) = map.get_mut((cy, cx)) {
-        if !*target {
-            return;
-        }
-        target
-    } else {
-        return;
-    };
-
-    *target = false;
-    *sum += 1;
-
-    if cy != 0 {
-        dfssize(map, (cy - 1, cx), sum);
-    }
-    dfssize(map, (cy + 1, cx), sum);
-    if cx != 0 {
-        dfssize(map, (cy, cx - 1), sum);
-    }
-    dfssize(map, (cy, cx + 1), sum);
-}
-
 fn task2(input: &ParsedInput, task1: Task1Output) -> Result<usize> {
-    let mut floodmap = input.map.map(|&n| n != 9);
-    let mut floodmap = floodmap.view_mut();
+    let map = &input.map;
     Ok(task1
         .0
         .into_iter()
-        .map(|((cy, cx), _)| {
-            let mut out = 0;
-            dfssize(&mut floodmap, (cy, cx), &mut out);
-            out
+        .map(|(position, _)| {
+            flood_fill(position, |&position| {
+                map.neighbours(position).filter(|&n| map[n] != 9)
+            })
+            .len()
         })
         .sorted()
         .rev()
@@ -27,7 +27,7 @@ enum BracketSide {
     Close,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         character::complete::{char, line_ending},
         combinator::map,
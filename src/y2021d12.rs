@@ -1,6 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
-use aoc::*;
+use aoc::{
+    dot::Dot,
+    graph::{Graph, NodeId},
+    *,
+};
 
 #[derive(Debug)]
 struct ParsedInput<'a> {
@@ -12,7 +16,7 @@ struct Connection<'a> {
     right: &'a str,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput<'_>> {
     use nom::{
         character::complete::{alpha1, char, line_ending},
         combinator::map,
@@ -31,88 +35,107 @@ fn parse(input: &str) -> ParseResult<ParsedInput> {
 }
 
 struct Cavemap<'a> {
-    connections: HashMap<&'a str, Vec<&'a str>>,
+    caves: Graph<&'a str>,
+    start: NodeId,
+    end: NodeId,
 }
-impl<'a, 'b: 'a> FromIterator<&'b Connection<'a>> for Cavemap<'a> {
-    fn from_iter<T: IntoIterator<Item = &'b Connection<'a>>>(iter: T) -> Self {
-        Cavemap {
-            connections: iter.into_iter().fold(
-                HashMap::new(),
-                |mut map, Connection { left, right }| {
-                    map.entry(left).or_insert_with(Vec::new).push(right);
-                    map.entry(right).or_insert_with(Vec::new).push(left);
-                    map
-                },
-            ),
+impl<'a> Cavemap<'a> {
+    fn new(connections: &[Connection<'a>]) -> Result<Cavemap<'a>> {
+        let mut caves = Graph::new();
+        for Connection { left, right } in connections {
+            caves.add_undirected_edge(*left, *right, ());
         }
+        Ok(Cavemap {
+            start: caves.id(&"start").ok_or("No start cave")?,
+            end: caves.id(&"end").ok_or("No end cave")?,
+            caves,
+        })
     }
+
+    fn is_small(&self, cave: NodeId) -> bool {
+        is_small(self.caves.node(cave))
+    }
+}
+
+fn is_small(cave: &str) -> bool {
+    cave.chars().all(|c| c.is_ascii_lowercase())
+}
+
+/// Draws the caves, small caves as circles and big caves as boxes
+fn export_dot(input: &ParsedInput) -> Result<Dot> {
+    let map = Cavemap::new(&input.connections)?;
+    Ok(map.caves.to_dot(
+        false,
+        |&cave| {
+            let shape = match cave {
+                "start" | "end" => "doublecircle",
+                cave if is_small(cave) => "circle",
+                _ => "box",
+            };
+            vec![("shape", shape.to_string())]
+        },
+        |_| vec![],
+    ))
 }
 
-fn count_paths<'a>(
+fn count_paths(
     map: &Cavemap,
-    position: &'a str,
-    mut visited: HashSet<&'a str>,
+    position: NodeId,
+    mut visited: HashSet<NodeId>,
     paths_found: &mut usize,
 ) {
-    if position == "end" {
+    if position == map.end {
         *paths_found += 1;
         return;
     }
     visited.insert(position);
-    if let Some(candidates) = map.connections.get(position) {
-        for candidate in candidates {
-            if candidate.chars().all(|c| c.is_ascii_lowercase()) && visited.contains(candidate) {
-                continue;
-            }
-            count_paths(map, candidate, visited.clone(), paths_found);
+    for candidate in map.caves.successors(position) {
+        if map.is_small(candidate) && visited.contains(&candidate) {
+            continue;
         }
+        count_paths(map, candidate, visited.clone(), paths_found);
     }
 }
 
 fn task1(input: &ParsedInput) -> Result<usize> {
-    let map: Cavemap = input.connections.iter().collect();
+    let map = Cavemap::new(&input.connections)?;
     let mut count = 0;
-    count_paths(&map, "start", HashSet::new(), &mut count);
+    count_paths(&map, map.start, HashSet::new(), &mut count);
     Ok(count)
 }
 
-fn count_paths2<'a>(
+fn count_paths2(
     map: &Cavemap,
-    position: &'a str,
-    mut visited: HashSet<&'a str>,
+    position: NodeId,
+    mut visited: HashSet<NodeId>,
     double_visit: bool,
     paths_found: &mut usize,
 ) {
-    if position == "end" {
+    if position == map.end {
         *paths_found += 1;
         return;
     }
     visited.insert(position);
-    if let Some(candidates) = map.connections.get(position) {
-        for candidate in candidates {
-            match candidate {
-                &"start" => continue,
-                candidate
-                    if candidate.chars().all(|c| c.is_ascii_lowercase())
-                        && visited.contains(candidate) =>
-                {
-                    if double_visit {
-                        continue;
-                    } else {
-                        count_paths2(map, candidate, visited.clone(), true, paths_found);
-                    }
-                }
-                candidate => {
-                    count_paths2(map, candidate, visited.clone(), double_visit, paths_found);
+    for candidate in map.caves.successors(position) {
+        match candidate {
+            candidate if candidate == map.start => continue,
+            candidate if map.is_small(candidate) && visited.contains(&candidate) => {
+                if double_visit {
+                    continue;
+                } else {
+                    count_paths2(map, candidate, visited.clone(), true, paths_found);
                 }
             }
+            candidate => {
+                count_paths2(map, candidate, visited.clone(), double_visit, paths_found);
+            }
         }
     }
 }
 fn task2(input: &ParsedInput) -> Result<usize> {
-    let map: Cavemap = input.connections.iter().collect();
+    let map = Cavemap::new(&input.connections)?;
     let mut count = 0;
-    count_paths2(&map, "start", HashSet::new(), false, &mut count);
+    count_paths2(&map, map.start, HashSet::new(), false, &mut count);
     Ok(count)
 }
 
@@ -171,6 +194,14 @@ start-RW
     assert_task!(parse, task2, t1, 36);
     assert_task!(parse, task2, t2, 103);
     assert_task!(parse, task2, t3, 3509);
+
+    let graph = export_dot(&parse_all(t1, parse(t1)).unwrap()).unwrap().to_string();
+    assert!(graph.starts_with("graph {\n"));
+    assert!(graph.contains("[label=\"start\", shape=\"doublecircle\"]"));
+    assert!(graph.contains("[label=\"A\", shape=\"box\"]"));
+    assert!(graph.contains("[label=\"c\", shape=\"circle\"]"));
+    assert_eq!(graph.matches(" -- ").count(), 7);
 }
 
 aoc_main!(parse, task1, task2);
+aoc_dot!(parse, export_dot);
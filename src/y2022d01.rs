@@ -15,16 +15,12 @@ impl Elf {
     }
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
-    use nom::{
-        character::complete::{i32 as number, line_ending},
-        multi::separated_list1,
-        Parser,
-    };
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
+    use aoc::parsers::{blocks, lines};
+    use nom::{character::complete::i32 as number, Parser};
 
-    let elf = separated_list1(line_ending, number).map(|inventory| Elf { inventory });
-    let mut parser =
-        separated_list1(line_ending.and(line_ending), elf).map(|elves| ParsedInput { elves });
+    let elf = lines(number).map(|inventory| Elf { inventory });
+    let mut parser = blocks(elf).map(|elves| ParsedInput { elves });
 
     parser.parse(input)
 }
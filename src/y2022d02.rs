@@ -104,7 +104,7 @@ struct Record {
     rhs: Rhs,
 }
 
-fn parse(input: &str) -> ParseResult<ParsedInput> {
+fn parse(input: &str) -> ParseResult<'_, ParsedInput> {
     use nom::{
         bytes::complete::tag, character::complete::line_ending, combinator::value,
         multi::separated_list1, Parser,
@@ -24,7 +24,7 @@ fn item_to_priority(item: char) -> i32 {
     }
 }
 
-fn parse<'input>(input: &'input str) -> ParseResult<ParsedInput<'input>> {
+fn parse<'input>(input: &'input str) -> ParseResult<'input, ParsedInput<'input>> {
     use nom::{
         character::complete::{alpha1, line_ending},
         multi::separated_list1,